@@ -0,0 +1,301 @@
+use crate::transliterate::transliterate;
+use crate::{CupFile, Error};
+use std::collections::{HashMap, HashSet};
+
+/// Which waypoints [`CupFile::generate_codes()`] assigns new codes to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CodeScope {
+    /// Only waypoints with an empty code
+    #[default]
+    Missing,
+    /// Waypoints with an empty code, a code longer than the maximum length,
+    /// or a code already used by a previous waypoint
+    Invalid,
+    /// All waypoints
+    All,
+}
+
+/// Configuration for [`CupFile::generate_codes()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeOptions {
+    /// Maximum number of characters of a code
+    pub max_length: usize,
+    /// Whether a leading number of the name, e.g. `002` in
+    /// `002 Aachen Brand`, is kept as prefix of the code
+    pub numeric_prefix: bool,
+    /// Which waypoints get a new code
+    pub scope: CodeScope,
+}
+
+impl Default for CodeOptions {
+    fn default() -> Self {
+        Self {
+            max_length: 8,
+            numeric_prefix: true,
+            scope: CodeScope::default(),
+        }
+    }
+}
+
+/// Code assigned by [`CupFile::generate_codes()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeChange {
+    /// Name of the waypoint
+    pub name: String,
+    /// Previous code, possibly empty
+    pub old: String,
+    /// Generated code
+    pub new: String,
+}
+
+/// Generator for unique, upper-case waypoint codes
+///
+/// Codes are built from the name: an optional numeric prefix followed by an
+/// abbreviation that keeps the first letter and the consonants of every
+/// word, e.g. `002ACBRA` for `002 Aachen Brand Abfahrt` with a maximum
+/// length of 8. Clashes are resolved by replacing the end of the code with
+/// a counter in base 36 (`2`-`9`, then `A`-`Z`, then `10` and so on).
+#[derive(Debug, Clone)]
+pub struct CodeGenerator {
+    max_length: usize,
+    numeric_prefix: bool,
+    used: HashSet<String>,
+}
+
+impl CodeGenerator {
+    /// Creates a generator for codes of at most `max_length` characters
+    pub fn new(max_length: usize, numeric_prefix: bool) -> Self {
+        Self {
+            max_length: max_length.max(1),
+            numeric_prefix,
+            used: HashSet::new(),
+        }
+    }
+
+    /// Marks an existing code as taken, ignoring case
+    ///
+    /// Returns `false` if the code was already taken.
+    pub fn reserve(&mut self, code: &str) -> bool {
+        self.used.insert(code.to_uppercase())
+    }
+
+    /// Whether the code is taken, ignoring case
+    pub fn is_used(&self, code: &str) -> bool {
+        self.used.contains(&code.to_uppercase())
+    }
+
+    /// Generates and reserves a unique code for the waypoint name
+    ///
+    /// Returns `None` if all codes derived from the name are taken, which
+    /// only happens when nearly all codes of the maximum length are used.
+    pub fn generate(&mut self, name: &str) -> Option<String> {
+        let base = self.abbreviate(name);
+        let code = match self.is_used(&base) {
+            false => base,
+            true => (2..)
+                .map(counter)
+                .take_while(|counter| counter.len() <= self.max_length)
+                .map(|counter| {
+                    let length = self.max_length - counter.len();
+                    let head = base.chars().take(length).collect::<String>();
+                    head + &counter
+                })
+                .find(|candidate| !self.is_used(candidate))?,
+        };
+        self.reserve(&code);
+        Some(code)
+    }
+
+    fn abbreviate(&self, name: &str) -> String {
+        let ascii = name
+            .chars()
+            .map(|c| transliterate(c).map_or_else(|| c.to_string(), str::to_string))
+            .collect::<String>()
+            .to_uppercase();
+
+        let mut words = ascii
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>();
+
+        let mut prefix = String::new();
+        let numeric = |word: &&str| word.chars().all(|c| c.is_ascii_digit());
+        if self.numeric_prefix && words.first().is_some_and(numeric) {
+            prefix = words.remove(0).chars().take(self.max_length).collect();
+        }
+
+        let length = self.max_length - prefix.len();
+        let abbreviation = match words.is_empty() {
+            true if prefix.is_empty() => "WP".chars().take(length).collect(),
+            true => String::new(),
+            false => abbreviate_words(&words, length),
+        };
+        prefix + &abbreviation
+    }
+}
+
+/// Formats `n` in base 36 with the digits `0`-`9` and `A`-`Z`
+fn counter(mut n: u64) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let mut digits = Vec::new();
+    loop {
+        digits.push(DIGITS[(n % 36) as usize]);
+        n /= 36;
+        if n == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().map(|&digit| digit as char).collect()
+}
+
+/// Shortens the words to at most `length` characters in total
+fn abbreviate_words(words: &[&str], length: usize) -> String {
+    let joined = words.concat();
+    if joined.len() <= length {
+        return joined;
+    }
+
+    // Keep the first letter and the consonants of every word
+    let consonants = words
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().into_iter();
+            first
+                .chain(chars.filter(|c| !"AEIOUY".contains(*c)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Take characters from the words in turn until the length is reached
+    let mut taken = vec![0; consonants.len()];
+    let mut remaining = length;
+    while remaining > 0 {
+        let before = remaining;
+        for (word, count) in consonants.iter().zip(&mut taken) {
+            if remaining > 0 && *count < word.len() {
+                *count += 1;
+                remaining -= 1;
+            }
+        }
+        if remaining == before {
+            break;
+        }
+    }
+
+    consonants
+        .iter()
+        .zip(taken)
+        .flat_map(|(word, count)| &word[..count])
+        .collect()
+}
+
+impl CupFile {
+    /// Assigns unique codes to the waypoints selected by
+    /// [`CodeOptions::scope`]
+    ///
+    /// Codes that are kept are reserved first, so generated codes never
+    /// clash with them. Tasks refer to waypoints by name and stay valid;
+    /// inline `Point=` definitions of a task take over the code of the
+    /// waypoint with the same name, or get a generated code as well.
+    ///
+    /// Fails without changing the file if the codes of the maximum length
+    /// are used up.
+    pub fn generate_codes(&mut self, options: &CodeOptions) -> Result<Vec<CodeChange>, Error> {
+        let mut generator = CodeGenerator::new(options.max_length, options.numeric_prefix);
+        let generate = |generator: &mut CodeGenerator, name: &str| {
+            generator
+                .generate(name)
+                .ok_or_else(|| Error::CodesExhausted(name.to_string()))
+        };
+
+        // Decide which codes are kept before generating any new ones
+        let mut seen = HashSet::new();
+        let regenerate = self
+            .waypoints
+            .iter()
+            .map(|waypoint| {
+                let code = &waypoint.code;
+                let unique = seen.insert(code.to_uppercase());
+                match options.scope {
+                    CodeScope::Missing => code.is_empty(),
+                    CodeScope::Invalid => {
+                        code.is_empty() || code.chars().count() > options.max_length || !unique
+                    }
+                    CodeScope::All => true,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for (waypoint, _) in self.waypoints.iter().zip(&regenerate).filter(|(_, r)| !**r) {
+            generator.reserve(&waypoint.code);
+        }
+
+        let mut codes = Vec::with_capacity(self.waypoints.len());
+        for (waypoint, regenerate) in self.waypoints.iter().zip(regenerate) {
+            codes.push(match regenerate {
+                true => generate(&mut generator, &waypoint.name)?,
+                false => waypoint.code.clone(),
+            });
+        }
+
+        // Inline points with the same name share one code
+        let mut shared = self
+            .waypoints
+            .iter()
+            .zip(&codes)
+            .map(|(waypoint, code)| (waypoint.name.clone(), code.clone()))
+            .collect::<HashMap<_, _>>();
+
+        let mut point_codes = Vec::new();
+        for point in self
+            .tasks
+            .iter()
+            .flat_map(|task| &task.points)
+            .map(|(_, point)| point)
+        {
+            if let Some(code) = shared.get(&point.name) {
+                point_codes.push(code.clone());
+                continue;
+            }
+
+            let replace = match options.scope {
+                CodeScope::Missing => point.code.is_empty(),
+                CodeScope::Invalid => {
+                    point.code.is_empty()
+                        || point.code.chars().count() > options.max_length
+                        || generator.is_used(&point.code)
+                }
+                CodeScope::All => true,
+            };
+            let code = match replace {
+                true => generate(&mut generator, &point.name)?,
+                false => {
+                    generator.reserve(&point.code);
+                    point.code.clone()
+                }
+            };
+            shared.insert(point.name.clone(), code.clone());
+            point_codes.push(code);
+        }
+
+        let mut changes = Vec::new();
+        for (waypoint, code) in self.waypoints.iter_mut().zip(codes) {
+            if code != waypoint.code {
+                changes.push(CodeChange {
+                    name: waypoint.name.clone(),
+                    old: std::mem::replace(&mut waypoint.code, code.clone()),
+                    new: code,
+                });
+            }
+        }
+
+        let points = self.tasks.iter_mut().flat_map(|task| &mut task.points);
+        for ((_, point), code) in points.zip(point_codes) {
+            point.code = code;
+        }
+
+        Ok(changes)
+    }
+}
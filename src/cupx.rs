@@ -0,0 +1,220 @@
+use crate::error::ParseIssue;
+use crate::{CupFile, Error, Warning};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Name of the waypoint file inside the archive
+const POINTS: &str = "points.cup";
+/// Folder of the pictures inside the archive
+const PICTURES: &str = "pics/";
+
+/// SeeYou CUPX archive: a zip file with the waypoints, optional task files
+/// and the pictures referenced by [`Waypoint::pictures`](crate::Waypoint::pictures)
+///
+/// File and folder names inside the archive are matched ignoring case.
+/// Other entries are ignored when reading.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CupxFile {
+    /// Contents of `points.cup`
+    pub cup: CupFile,
+    /// Other `.cup` files, usually holding tasks, keyed by their path in
+    /// the archive
+    pub task_files: BTreeMap<String, CupFile>,
+    /// Contents of the files in the `pics/` folder, keyed by file name
+    pub pictures: BTreeMap<String, Vec<u8>>,
+}
+
+/// Result of [`CupxFile::validate_pictures()`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PictureReport {
+    /// Pictures referenced by a waypoint but missing from the archive
+    pub missing: Vec<MissingPicture>,
+    /// Pictures in the archive that no waypoint refers to
+    pub orphans: Vec<String>,
+}
+
+/// Picture referenced by a waypoint but missing from the archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingPicture {
+    /// Name of the waypoint
+    pub waypoint: String,
+    /// File name of the picture
+    pub picture: String,
+}
+
+impl PictureReport {
+    /// Whether all pictures are present and referenced
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.orphans.is_empty()
+    }
+}
+
+impl CupxFile {
+    /// Reads an archive, returning the warnings of all contained CUP files
+    ///
+    /// SeeYou writes CUPX files as two zip archives joined together: the
+    /// pictures in the first one and `points.cup` in the second one. The
+    /// entries of both archives are read.
+    pub fn from_reader<R: Read + Seek>(reader: R) -> Result<(Self, Vec<Warning>), Error> {
+        let mut cup = None;
+        let mut cupx = CupxFile::default();
+        let mut warnings = Vec::new();
+
+        // The last archive is found from the end of the file, and starts
+        // after the end of central directory record of the first one
+        let mut archive = ZipArchive::new(reader)?;
+        let offset = archive.offset();
+        read_entries(&mut archive, &mut cup, &mut cupx, &mut warnings)?;
+
+        if offset > 0 {
+            let mut reader = archive.into_inner();
+            reader.seek(SeekFrom::Start(0))?;
+            let mut bytes = Vec::new();
+            reader.take(offset).read_to_end(&mut bytes)?;
+
+            // Data in front of the archive that isn't a zip file is ignored
+            if let Ok(mut first) = ZipArchive::new(Cursor::new(bytes)) {
+                read_entries(&mut first, &mut cup, &mut cupx, &mut warnings)?;
+            }
+        }
+
+        cupx.cup =
+            cup.ok_or_else(|| ParseIssue::new(format!("Missing {POINTS} in CUPX archive")))?;
+        Ok((cupx, warnings))
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<(Self, Vec<Warning>), Error> {
+        let file = File::open(path)?;
+        Self::from_reader(file)
+    }
+
+    /// Writes the archive with `points.cup` and the task files in UTF-8,
+    /// and the pictures in the `pics/` folder
+    pub fn to_writer<W: Write + Seek>(&self, writer: W) -> Result<(), Error> {
+        let mut zip = ZipWriter::new(writer);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip.start_file(POINTS, options)?;
+        self.cup.to_writer(&mut zip)?;
+
+        for (name, tasks) in &self.task_files {
+            zip.start_file(name, options)?;
+            tasks.to_writer(&mut zip)?;
+        }
+
+        // Pictures are compressed already
+        let options = options.compression_method(CompressionMethod::Stored);
+        for (name, bytes) in &self.pictures {
+            zip.start_file(format!("{PICTURES}{name}"), options)?;
+            zip.write_all(bytes)?;
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
+
+    pub fn to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let file = File::create(path)?;
+        self.to_writer(file)
+    }
+
+    /// Writes a KMZ archive for Google Earth with the KML of
+    /// [`CupxFile::cup`] and the pictures in the `pics/` folder
+    ///
+    /// See [`CupFile::to_kml_writer()`].
+    pub fn to_kmz_writer<W: Write + Seek>(&self, writer: W) -> Result<(), Error> {
+        let kml = self.cup.to_kml_string()?;
+
+        let mut zip = ZipWriter::new(writer);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip.start_file("doc.kml", options)?;
+        zip.write_all(kml.as_bytes())?;
+
+        let options = options.compression_method(CompressionMethod::Stored);
+        for (name, bytes) in &self.pictures {
+            zip.start_file(format!("{PICTURES}{name}"), options)?;
+            zip.write_all(bytes)?;
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
+
+    pub fn to_kmz_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let file = File::create(path)?;
+        self.to_kmz_writer(file)
+    }
+
+    /// Checks that every picture referenced by a waypoint of
+    /// [`CupxFile::cup`], its inline task points or the task files is part
+    /// of the archive, and that every picture is referenced
+    pub fn validate_pictures(&self) -> PictureReport {
+        let files = std::iter::once(&self.cup).chain(self.task_files.values());
+        let waypoints = files.flat_map(|cup| {
+            let inline = cup.tasks.iter().flat_map(|task| &task.points);
+            cup.waypoints
+                .iter()
+                .chain(inline.map(|(_, waypoint)| waypoint))
+        });
+
+        let mut report = PictureReport::default();
+        let mut referenced = BTreeSet::new();
+        for waypoint in waypoints {
+            for picture in &waypoint.pictures {
+                referenced.insert(picture.as_str());
+                let missing = MissingPicture {
+                    waypoint: waypoint.name.clone(),
+                    picture: picture.clone(),
+                };
+                if !self.pictures.contains_key(picture) && !report.missing.contains(&missing) {
+                    report.missing.push(missing);
+                }
+            }
+        }
+
+        report.orphans = self
+            .pictures
+            .keys()
+            .filter(|name| !referenced.contains(name.as_str()))
+            .cloned()
+            .collect();
+        report
+    }
+}
+
+/// Adds the entries of one zip archive of a CUPX file
+fn read_entries<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    cup: &mut Option<CupFile>,
+    cupx: &mut CupxFile,
+    warnings: &mut Vec<Warning>,
+) -> Result<(), Error> {
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        if file.is_dir() {
+            continue;
+        }
+
+        let name = file.name().to_string();
+        let lowercase = name.to_lowercase();
+        if lowercase == POINTS {
+            let (points, points_warnings) = CupFile::from_reader(&mut file)?;
+            *cup = Some(points);
+            warnings.extend(points_warnings);
+        } else if lowercase.ends_with(".cup") {
+            let (tasks, task_warnings) = CupFile::from_reader(&mut file)?;
+            cupx.task_files.insert(name, tasks);
+            warnings.extend(task_warnings);
+        } else if lowercase.starts_with(PICTURES) {
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes)?;
+            cupx.pictures
+                .insert(name[PICTURES.len()..].to_string(), bytes);
+        }
+    }
+    Ok(())
+}
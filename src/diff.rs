@@ -0,0 +1,299 @@
+use crate::geodesy::EarthModel;
+use crate::writer::format_field;
+use crate::{Column, CupFile, Distance, Task, Waypoint};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+const MODEL: EarthModel = EarthModel::Wgs84;
+
+/// Maximum distance in meters for matching waypoints by position
+const MATCH_DISTANCE: f64 = 100.0;
+
+/// Differences between two files, created by [`CupFile::diff()`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CupDiff {
+    /// Changed waypoints, in the order of the new file followed by the removed ones
+    pub waypoints: Vec<WaypointChange>,
+    /// Changed tasks, in the order of the new file followed by the removed ones
+    pub tasks: Vec<TaskChange>,
+}
+
+/// Change of a single waypoint in a [`CupDiff`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum WaypointChange {
+    /// Waypoint only present in the new file
+    Added { name: String },
+    /// Waypoint only present in the old file
+    Removed { name: String },
+    /// Waypoint present in both files with differences
+    Modified(WaypointModification),
+}
+
+/// Differences of a waypoint present in both files
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaypointModification {
+    /// Name in the old file
+    pub old_name: String,
+    /// Name in the new file
+    pub new_name: String,
+    /// Distance the waypoint moved, if its position changed
+    pub moved: Option<Distance>,
+    /// Other fields with different values
+    pub fields: Vec<FieldChange>,
+}
+
+/// Changed waypoint field, formatted as in the CUP file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub column: Column,
+    pub old: String,
+    pub new: String,
+}
+
+/// Change of a single task in a [`CupDiff`]
+///
+/// Tasks are identified by their description, or by `#n` (counting from 1)
+/// if they have none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskChange {
+    /// Task only present in the new file
+    Added { task: String },
+    /// Task only present in the old file
+    Removed { task: String },
+    /// Task present in both files with differences
+    Modified { task: String, parts: Vec<TaskPart> },
+}
+
+/// Part of a task that changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskPart {
+    /// Waypoint names, apart from renamed waypoints
+    Points,
+    /// Task options
+    Options,
+    /// Observation zones
+    ObservationZones,
+    /// Inline `Point=` definitions
+    InlinePoints,
+    /// Alternative start points
+    MultipleStarts,
+}
+
+impl CupDiff {
+    /// Whether both files are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.waypoints.is_empty() && self.tasks.is_empty()
+    }
+}
+
+impl CupFile {
+    /// Compares the waypoints and tasks of this (old) file with `other` (new)
+    ///
+    /// Waypoints are matched by name, then by code and finally by a
+    /// position within 100 m, regardless of their order in the files. Tasks
+    /// are matched by description, and tasks without description by their
+    /// order. Task references to renamed waypoints don't count as changes.
+    pub fn diff(&self, other: &CupFile) -> CupDiff {
+        let pairs = match_waypoints(&self.waypoints, &other.waypoints);
+
+        let mut matched_old = vec![false; self.waypoints.len()];
+        let mut renames = HashMap::new();
+        let mut waypoints = Vec::new();
+
+        for (new_index, new) in other.waypoints.iter().enumerate() {
+            let Some(old_index) = pairs[new_index] else {
+                let name = new.name.clone();
+                waypoints.push(WaypointChange::Added { name });
+                continue;
+            };
+
+            matched_old[old_index] = true;
+            let old = &self.waypoints[old_index];
+            if old.name != new.name {
+                renames.insert(old.name.as_str(), new.name.as_str());
+            }
+            if let Some(modification) = compare_waypoints(old, new) {
+                waypoints.push(WaypointChange::Modified(modification));
+            }
+        }
+
+        for (old, _) in self.waypoints.iter().zip(matched_old).filter(|(_, m)| !m) {
+            let name = old.name.clone();
+            waypoints.push(WaypointChange::Removed { name });
+        }
+
+        let tasks = diff_tasks(&self.tasks, &other.tasks, &renames);
+        CupDiff { waypoints, tasks }
+    }
+}
+
+/// Index of the matching old waypoint for every new waypoint
+fn match_waypoints(old: &[Waypoint], new: &[Waypoint]) -> Vec<Option<usize>> {
+    let mut pairs = vec![None; new.len()];
+    let mut taken = vec![false; old.len()];
+
+    let passes: [fn(&Waypoint, &Waypoint) -> bool; 3] = [
+        |a, b| a.name == b.name,
+        |a, b| !a.code.is_empty() && a.code == b.code,
+        |a, b| MODEL.inverse(a.position(), b.position()).0 <= MATCH_DISTANCE,
+    ];
+
+    for matches in passes {
+        for (new_index, new) in new.iter().enumerate() {
+            if pairs[new_index].is_some() {
+                continue;
+            }
+            let found = (0..old.len()).find(|&i| !taken[i] && matches(&old[i], new));
+            if let Some(old_index) = found {
+                taken[old_index] = true;
+                pairs[new_index] = Some(old_index);
+            }
+        }
+    }
+
+    pairs
+}
+
+fn compare_waypoints(old: &Waypoint, new: &Waypoint) -> Option<WaypointModification> {
+    let moved = (old.position() != new.position()).then(|| {
+        let (meters, _) = MODEL.inverse(old.position(), new.position());
+        Distance::Meters(meters)
+    });
+
+    let fields = Column::ALL
+        .into_iter()
+        .filter(|column| !matches!(column, Column::Name | Column::Lat | Column::Lon))
+        .filter_map(|column| {
+            let old = format_field(old, column);
+            let new = format_field(new, column);
+            (old != new).then_some(FieldChange { column, old, new })
+        })
+        .collect::<Vec<_>>();
+
+    if old.name == new.name && moved.is_none() && fields.is_empty() {
+        return None;
+    }
+
+    Some(WaypointModification {
+        old_name: old.name.clone(),
+        new_name: new.name.clone(),
+        moved,
+        fields,
+    })
+}
+
+fn diff_tasks(old: &[Task], new: &[Task], renames: &HashMap<&str, &str>) -> Vec<TaskChange> {
+    let label = |task: &Task, index: usize| match &task.description {
+        Some(description) => description.clone(),
+        None => format!("#{}", index + 1),
+    };
+
+    let mut taken = vec![false; old.len()];
+    let mut changes = Vec::new();
+
+    for (new_index, new_task) in new.iter().enumerate() {
+        // Tasks without description are matched with the next free one
+        let found =
+            (0..old.len()).find(|&i| !taken[i] && old[i].description == new_task.description);
+        let Some(old_index) = found else {
+            let task = label(new_task, new_index);
+            changes.push(TaskChange::Added { task });
+            continue;
+        };
+
+        taken[old_index] = true;
+        let parts = compare_tasks(&old[old_index], new_task, renames);
+        if !parts.is_empty() {
+            let task = label(new_task, new_index);
+            changes.push(TaskChange::Modified { task, parts });
+        }
+    }
+
+    for (old_index, old_task) in old.iter().enumerate() {
+        if !taken[old_index] {
+            let task = label(old_task, old_index);
+            changes.push(TaskChange::Removed { task });
+        }
+    }
+
+    changes
+}
+
+fn compare_tasks(old: &Task, new: &Task, renames: &HashMap<&str, &str>) -> Vec<TaskPart> {
+    let mut renamed = old.clone();
+    renamed.rename_references(|name| renames.get(name).map(ToString::to_string));
+
+    let mut parts = Vec::new();
+    if renamed.waypoint_names != new.waypoint_names {
+        parts.push(TaskPart::Points);
+    }
+    if old.options != new.options {
+        parts.push(TaskPart::Options);
+    }
+    if old.observation_zones != new.observation_zones {
+        parts.push(TaskPart::ObservationZones);
+    }
+    if old.points != new.points {
+        parts.push(TaskPart::InlinePoints);
+    }
+    if renamed.multiple_starts != new.multiple_starts {
+        parts.push(TaskPart::MultipleStarts);
+    }
+    parts
+}
+
+impl Display for CupDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for change in &self.waypoints {
+            writeln!(f, "{change}")?;
+        }
+        for change in &self.tasks {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for WaypointChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WaypointChange::Added { name } => write!(f, "+ Waypoint '{name}'"),
+            WaypointChange::Removed { name } => write!(f, "- Waypoint '{name}'"),
+            WaypointChange::Modified(modification) => {
+                let mut details = Vec::new();
+                if modification.old_name != modification.new_name {
+                    details.push(format!("renamed to '{}'", modification.new_name));
+                }
+                if let Some(moved) = &modification.moved {
+                    details.push(format!("moved by {:.0}m", moved.to_meters()));
+                }
+                for field in &modification.fields {
+                    let column = field.column.header();
+                    details.push(format!("{column}: '{}' -> '{}'", field.old, field.new));
+                }
+                let name = &modification.old_name;
+                write!(f, "~ Waypoint '{name}': {}", details.join(", "))
+            }
+        }
+    }
+}
+
+impl Display for TaskChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskChange::Added { task } => write!(f, "+ Task '{task}'"),
+            TaskChange::Removed { task } => write!(f, "- Task '{task}'"),
+            TaskChange::Modified { task, parts } => {
+                let parts = parts.iter().map(|part| match part {
+                    TaskPart::Points => "points",
+                    TaskPart::Options => "options",
+                    TaskPart::ObservationZones => "observation zones",
+                    TaskPart::InlinePoints => "inline points",
+                    TaskPart::MultipleStarts => "multiple starts",
+                });
+                let parts = parts.collect::<Vec<_>>().join(", ");
+                write!(f, "~ Task '{task}': {parts}")
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{CupFile, Error, Waypoint};
+
+/// What [`CupFile::remove_waypoint()`] does with tasks referring to the waypoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RemovePolicy {
+    /// Fail with [`Error::WaypointInUse`] if any task refers to the waypoint
+    #[default]
+    Refuse,
+    /// Remove the references from the tasks, together with their
+    /// observation zones
+    ///
+    /// Takeoff and landing references are replaced by an empty name.
+    DropFromTasks,
+    /// Keep the tasks unchanged by adding the waypoint as inline `Point=`
+    /// definition wherever it is used
+    ///
+    /// Alternative start points can't be defined inline and are removed.
+    Inline,
+}
+
+impl CupFile {
+    /// Renames the waypoint and updates all task references to it
+    ///
+    /// Fails if there is no waypoint named `old`, or if another waypoint
+    /// is already named `new`.
+    pub fn rename_waypoint(&mut self, old: &str, new: &str) -> Result<(), Error> {
+        let index = self.waypoint_index(old)?;
+        self.rename_references(old, new)?;
+        self.waypoints[index].name = new.to_string();
+        Ok(())
+    }
+
+    /// Removes the waypoint and returns it
+    ///
+    /// Task references to the waypoint are handled according to `policy`.
+    /// Inline `Point=` definitions with the same name are not affected.
+    pub fn remove_waypoint(&mut self, name: &str, policy: RemovePolicy) -> Result<Waypoint, Error> {
+        let index = self.waypoint_index(name)?;
+        let used = self.tasks.iter().any(|task| task.refers_to(name));
+
+        match policy {
+            _ if !used => {}
+            RemovePolicy::Refuse => return Err(Error::WaypointInUse(name.to_string())),
+            RemovePolicy::DropFromTasks => {
+                for task in &mut self.tasks {
+                    let drop = task.reference_indices(name);
+                    task.remove_points(|index, _| !drop.contains(&index));
+                    task.multiple_starts.retain(|start| start != name);
+                }
+            }
+            RemovePolicy::Inline => {
+                let waypoint = &self.waypoints[index];
+                for task in &mut self.tasks {
+                    let points = task.reference_indices(name).into_iter();
+                    let points = points.map(|index| (index as u32, waypoint.clone()));
+                    task.points.extend(points);
+                    task.points.sort_by_key(|(index, _)| *index);
+                    task.multiple_starts.retain(|start| start != name);
+                }
+            }
+        }
+
+        Ok(self.waypoints.remove(index))
+    }
+
+    /// Replaces the waypoint named `name` and returns the previous one
+    ///
+    /// If the new waypoint has a different name, task references are
+    /// updated as with [`CupFile::rename_waypoint()`].
+    pub fn replace_waypoint(&mut self, name: &str, waypoint: Waypoint) -> Result<Waypoint, Error> {
+        let index = self.waypoint_index(name)?;
+        self.rename_references(name, &waypoint.name)?;
+        Ok(std::mem::replace(&mut self.waypoints[index], waypoint))
+    }
+
+    /// Updates the task references from `old` to `new`, unless `new` is
+    /// already taken by another waypoint
+    fn rename_references(&mut self, old: &str, new: &str) -> Result<(), Error> {
+        if old == new {
+            return Ok(());
+        }
+        if self.waypoints.iter().any(|waypoint| waypoint.name == new) {
+            return Err(Error::DuplicateWaypoint(new.to_string()));
+        }
+
+        for task in &mut self.tasks {
+            task.rename_references(|name| (name == old).then(|| new.to_string()));
+        }
+        Ok(())
+    }
+
+    fn waypoint_index(&self, name: &str) -> Result<usize, Error> {
+        self.waypoints
+            .iter()
+            .position(|waypoint| waypoint.name == name)
+            .ok_or_else(|| Error::UnknownWaypoint(name.to_string()))
+    }
+}
@@ -14,6 +14,36 @@ pub enum Error {
 
     #[error(transparent)]
     Csv(#[from] csv::Error),
+
+    #[cfg(feature = "cupx")]
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+
+    #[cfg(feature = "gpx")]
+    #[error("XML error: {0}")]
+    Xml(#[from] roxmltree::Error),
+
+    #[cfg(feature = "geojson")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unknown waypoint: '{0}'")]
+    UnknownWaypoint(String),
+
+    #[error("Duplicate waypoint: '{0}'")]
+    DuplicateWaypoint(String),
+
+    #[error("Waypoint is used by a task: '{0}'")]
+    WaypointInUse(String),
+
+    #[error("Unknown task index: {0}")]
+    UnknownTask(usize),
+
+    #[error("Invalid target: {0}")]
+    InvalidTarget(String),
+
+    #[error("No unique code left for waypoint: '{0}'")]
+    CodesExhausted(String),
 }
 
 impl From<ParseIssue> for Error {
@@ -23,21 +53,45 @@ impl From<ParseIssue> for Error {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Warning(ParseIssue);
+pub struct Warning {
+    issue: ParseIssue,
+    kind: WarningKind,
+}
+
+/// What a [`Warning`] was raised by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningKind {
+    /// Problem in the input that was skipped or repaired while reading
+    Parse,
+    /// Characters replaced while writing, because the target encoding
+    /// can't represent them
+    EncodingFallback,
+}
 
 impl Warning {
+    pub(crate) fn encoding_fallback(message: impl Into<String>) -> Self {
+        let issue = ParseIssue::new(message);
+        let kind = WarningKind::EncodingFallback;
+        Self { issue, kind }
+    }
+
     pub fn message(&self) -> &str {
-        &self.0.message
+        &self.issue.message
     }
 
     pub fn line(&self) -> Option<u64> {
-        self.0.line
+        self.issue.line
+    }
+
+    pub fn kind(&self) -> WarningKind {
+        self.kind
     }
 }
 
 impl From<ParseIssue> for Warning {
     fn from(issue: ParseIssue) -> Self {
-        Warning(issue)
+        let kind = WarningKind::Parse;
+        Self { issue, kind }
     }
 }
 
@@ -54,6 +108,13 @@ impl ParseIssue {
         Self { message, line }
     }
 
+    #[cfg(feature = "gpx")]
+    pub(crate) fn with_line(self, line: u64) -> Self {
+        let message = self.message;
+        let line = Some(line);
+        Self { message, line }
+    }
+
     pub(crate) fn with_record(self, record: &StringRecord) -> Self {
         let message = self.message;
         let line = record.position().map(|p| p.line());
@@ -0,0 +1,83 @@
+use crate::geodesy::EarthModel;
+use crate::{CupFile, Distance, Error};
+use std::collections::BTreeSet;
+
+const MODEL: EarthModel = EarthModel::Wgs84;
+
+impl CupFile {
+    /// Builds a file with only the task at `index` and the waypoints it
+    /// refers to, including its alternative start points
+    ///
+    /// The waypoints keep their order and the column layout is copied.
+    /// Fails if there is no task at `index`, or if the task refers to a
+    /// waypoint that isn't defined.
+    pub fn extract_task(&self, index: usize) -> Result<CupFile, Error> {
+        self.extract(index, None)
+    }
+
+    /// Like [`CupFile::extract_task()`], but also includes all landable
+    /// waypoints within `distance` of the course line from start to finish
+    pub fn extract_task_with_landables(
+        &self,
+        index: usize,
+        distance: &Distance,
+    ) -> Result<CupFile, Error> {
+        self.extract(index, Some(distance))
+    }
+
+    fn extract(&self, index: usize, landables: Option<&Distance>) -> Result<CupFile, Error> {
+        let task = self.tasks.get(index).ok_or(Error::UnknownTask(index))?;
+
+        let mut indices = BTreeSet::new();
+        let references = task
+            .waypoint_names
+            .iter()
+            .enumerate()
+            .filter(|(index, name)| {
+                let inline = task.points.iter().any(|(idx, _)| *idx as usize == *index);
+                !inline && !name.is_empty()
+            });
+        let names = references
+            .map(|(_, name)| name)
+            .chain(&task.multiple_starts);
+        for name in names {
+            let position = self
+                .waypoints
+                .iter()
+                .position(|waypoint| waypoint.name == *name)
+                .ok_or_else(|| Error::UnknownWaypoint(name.clone()))?;
+            indices.insert(position);
+        }
+
+        if let Some(distance) = landables {
+            let course = task
+                .resolve(&self.waypoints)?
+                .iter()
+                .map(|point| point.waypoint.position())
+                .collect::<Vec<_>>();
+            let legs = match course.len() {
+                1 => vec![(course[0], course[0])],
+                _ => course.windows(2).map(|leg| (leg[0], leg[1])).collect(),
+            };
+
+            let meters = distance.to_meters();
+            for (index, waypoint) in self.waypoints.iter().enumerate() {
+                let near = legs.iter().any(|(from, to)| {
+                    MODEL.distance_to_segment(waypoint.position(), *from, *to) <= meters
+                });
+                if waypoint.style.is_landable() && near {
+                    indices.insert(index);
+                }
+            }
+        }
+
+        Ok(CupFile {
+            waypoints: indices
+                .into_iter()
+                .map(|i| self.waypoints[i].clone())
+                .collect(),
+            tasks: vec![task.clone()],
+            columns: self.columns.clone(),
+        })
+    }
+}
@@ -0,0 +1,273 @@
+mod query;
+
+use crate::{Coordinate, CupFile, Elevation, Task, Waypoint, WaypointStyle};
+use std::collections::HashSet;
+use std::ops::Not;
+
+/// Composable predicate on waypoints
+///
+/// Filters can be built from the variants directly, combined with
+/// [`WaypointFilter::and()`], [`WaypointFilter::or()`] and `!`, or parsed
+/// from a query:
+///
+/// ```
+/// use seeyou_cup::WaypointFilter;
+///
+/// let filter: WaypointFilter = r#"style in [2, 3, 4, 5] and country = "de""#.parse().unwrap();
+/// let filter = filter.and("elev >= 300m".parse().unwrap());
+/// ```
+///
+/// Queries compare the fields `name`, `code`, `country`, `desc`, `style`
+/// and `elev` with `=`, `!=`, `<`, `<=`, `>`, `>=` and `in [...]`, and
+/// combine them with `and`, `or`, `not` and parentheses. The keyword
+/// `landable` matches all landable waypoints. Text comparisons ignore case,
+/// and `*` and `?` in names, codes and descriptions are wildcards.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WaypointFilter {
+    /// Name matching a pattern with `*` and `?` wildcards, ignoring case
+    Name(String),
+    /// Code matching a pattern with `*` and `?` wildcards, ignoring case
+    Code(String),
+    /// Description matching a pattern with `*` and `?` wildcards, ignoring case
+    Description(String),
+    /// Country code, ignoring case
+    Country(String),
+    /// One of the given styles
+    Style(Vec<WaypointStyle>),
+    /// Styles for which [`WaypointStyle::is_landable()`] is true
+    Landable,
+    /// Elevation compared to the given value
+    Elevation(Comparison, Elevation),
+    /// Position inside the box from south-west to north-east corner
+    ///
+    /// The box crosses the antimeridian if the south-west corner lies east
+    /// of the north-east corner.
+    BoundingBox {
+        south_west: Coordinate,
+        north_east: Coordinate,
+    },
+    /// Position inside the polygon, treating latitude and longitude as
+    /// plane coordinates
+    Polygon(Vec<Coordinate>),
+    /// All filters match
+    And(Vec<WaypointFilter>),
+    /// Any filter matches
+    Or(Vec<WaypointFilter>),
+    /// The filter doesn't match
+    Not(Box<WaypointFilter>),
+}
+
+/// Comparison operator of [`WaypointFilter::Elevation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// What [`CupFile::filter()`] does with tasks referring to removed waypoints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TaskFilter {
+    /// Keep all tasks unchanged
+    #[default]
+    Keep,
+    /// Drop tasks referring to any removed waypoint
+    Drop,
+    /// Remove the references from the tasks, and drop tasks with fewer
+    /// than two remaining points
+    Prune,
+}
+
+impl Comparison {
+    fn test(self, value: f64, reference: f64) -> bool {
+        match self {
+            Comparison::Equal => value == reference,
+            Comparison::NotEqual => value != reference,
+            Comparison::Less => value < reference,
+            Comparison::LessOrEqual => value <= reference,
+            Comparison::Greater => value > reference,
+            Comparison::GreaterOrEqual => value >= reference,
+        }
+    }
+}
+
+impl WaypointFilter {
+    /// Whether the waypoint matches the filter
+    pub fn matches(&self, waypoint: &Waypoint) -> bool {
+        match self {
+            WaypointFilter::Name(pattern) => glob_match(pattern, &waypoint.name),
+            WaypointFilter::Code(pattern) => glob_match(pattern, &waypoint.code),
+            WaypointFilter::Description(pattern) => glob_match(pattern, &waypoint.description),
+            WaypointFilter::Country(country) => waypoint.country.eq_ignore_ascii_case(country),
+            WaypointFilter::Style(styles) => styles.contains(&waypoint.style),
+            WaypointFilter::Landable => waypoint.style.is_landable(),
+            WaypointFilter::Elevation(comparison, elevation) => {
+                let value = waypoint.elevation.to_meters();
+                comparison.test(value, elevation.to_meters())
+            }
+            WaypointFilter::BoundingBox {
+                south_west,
+                north_east,
+            } => {
+                let (south, west) = south_west.position();
+                let (north, east) = north_east.position();
+                let (lat, lon) = waypoint.position();
+                let in_longitude = match west <= east {
+                    true => (west..=east).contains(&lon),
+                    false => lon >= west || lon <= east,
+                };
+                (south..=north).contains(&lat) && in_longitude
+            }
+            WaypointFilter::Polygon(corners) => polygon_contains(corners, waypoint.position()),
+            WaypointFilter::And(filters) => filters.iter().all(|f| f.matches(waypoint)),
+            WaypointFilter::Or(filters) => filters.iter().any(|f| f.matches(waypoint)),
+            WaypointFilter::Not(filter) => !filter.matches(waypoint),
+        }
+    }
+
+    /// Filter matching if both filters match
+    pub fn and(self, other: WaypointFilter) -> Self {
+        match self {
+            WaypointFilter::And(mut filters) => {
+                filters.push(other);
+                WaypointFilter::And(filters)
+            }
+            filter => WaypointFilter::And(vec![filter, other]),
+        }
+    }
+
+    /// Filter matching if any of the filters matches
+    pub fn or(self, other: WaypointFilter) -> Self {
+        match self {
+            WaypointFilter::Or(mut filters) => {
+                filters.push(other);
+                WaypointFilter::Or(filters)
+            }
+            filter => WaypointFilter::Or(vec![filter, other]),
+        }
+    }
+}
+
+impl Not for WaypointFilter {
+    type Output = WaypointFilter;
+
+    /// Filter matching if this filter doesn't match
+    fn not(self) -> Self::Output {
+        WaypointFilter::Not(Box::new(self))
+    }
+}
+
+impl CupFile {
+    /// Returns a copy of the file with only the waypoints matching `filter`
+    ///
+    /// `tasks` controls what happens to tasks referring to removed
+    /// waypoints. Inline `Point=` definitions are never removed.
+    pub fn filter(&self, filter: &WaypointFilter, tasks: TaskFilter) -> CupFile {
+        let (waypoints, removed): (Vec<_>, Vec<_>) = self
+            .waypoints
+            .iter()
+            .cloned()
+            .partition(|waypoint| filter.matches(waypoint));
+
+        let kept = waypoints
+            .iter()
+            .map(|w| w.name.as_str())
+            .collect::<HashSet<_>>();
+        let removed = removed
+            .iter()
+            .map(|w| w.name.as_str())
+            .filter(|name| !kept.contains(name))
+            .collect::<HashSet<_>>();
+
+        let is_removed = |task: &Task, index: usize, name: &str| {
+            let inline = task.points.iter().any(|(idx, _)| *idx as usize == index);
+            !inline && removed.contains(name)
+        };
+
+        let tasks = self
+            .tasks
+            .iter()
+            .filter_map(|task| match tasks {
+                TaskFilter::Keep => Some(task.clone()),
+                TaskFilter::Drop => {
+                    let mut names = task.waypoint_names.iter().enumerate();
+                    let broken = names.any(|(index, name)| is_removed(task, index, name))
+                        || task
+                            .multiple_starts
+                            .iter()
+                            .any(|s| removed.contains(s.as_str()));
+                    (!broken).then(|| task.clone())
+                }
+                TaskFilter::Prune => {
+                    let names = task.waypoint_names.iter().enumerate();
+                    let drop = names
+                        .map(|(index, name)| is_removed(task, index, name))
+                        .collect::<Vec<_>>();
+
+                    let mut task = task.clone();
+                    task.remove_points(|index, _| !drop[index]);
+                    task.multiple_starts
+                        .retain(|s| !removed.contains(s.as_str()));
+                    (task.course_names().len() >= 2).then_some(task)
+                }
+            })
+            .collect();
+
+        CupFile {
+            waypoints,
+            tasks,
+            columns: self.columns.clone(),
+        }
+    }
+}
+
+/// Matches `text` against `pattern` with `*` and `?` wildcards, ignoring case
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+    let text = text.to_lowercase().chars().collect::<Vec<_>>();
+
+    // Position after the last `*` in the pattern, and the text position it was tried at
+    let mut star: Option<(usize, usize)> = None;
+    let (mut p, mut t) = (0, 0);
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    star = Some((star_p, star_t + 1));
+                    p = star_p;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Ray casting point-in-polygon test in the latitude/longitude plane
+fn polygon_contains(corners: &[Coordinate], (lat, lon): (f64, f64)) -> bool {
+    let mut inside = false;
+    for (i, a) in corners.iter().enumerate() {
+        let b = &corners[(i + 1) % corners.len()];
+        let (lat_a, lon_a) = a.position();
+        let (lat_b, lon_b) = b.position();
+        if (lat_a > lat) != (lat_b > lat) {
+            let crossing = lon_a + (lat - lat_a) / (lat_b - lat_a) * (lon_b - lon_a);
+            if lon < crossing {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
@@ -0,0 +1,295 @@
+use crate::filter::{Comparison, WaypointFilter};
+use crate::parser::parse_waypoint_style;
+use crate::{Elevation, FromStr, WaypointStyle};
+
+/// Maximum nesting of parentheses, which are parsed recursively
+const MAX_DEPTH: usize = 64;
+
+/// Token of a filter query
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Keyword, field name or unquoted value like `500m`
+    Word(String),
+    /// Quoted string
+    Text(String),
+    Operator(Comparison),
+    OpenBracket,
+    CloseBracket,
+    OpenParen,
+    CloseParen,
+    Comma,
+}
+
+/// Value on the right side of a comparison
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Single(String),
+    List(Vec<String>),
+}
+
+impl FromStr for WaypointFilter {
+    type Err = String;
+
+    /// Parses a query like `style in [2,3,4,5] and country = "de"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut parser = QueryParser {
+            tokens,
+            position: 0,
+            depth: 0,
+        };
+
+        let filter = parser.parse_or()?;
+        match parser.peek() {
+            Some(token) => Err(format!("Invalid query: unexpected {}", describe(token))),
+            None => Ok(filter),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            ',' => Token::Comma,
+            '=' => Token::Operator(Comparison::Equal),
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Operator(Comparison::NotEqual),
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Operator(Comparison::LessOrEqual),
+            '<' => Token::Operator(Comparison::Less),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Operator(Comparison::GreaterOrEqual),
+            '>' => Token::Operator(Comparison::Greater),
+            '"' | '\'' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => text.push(next),
+                        None => return Err("Invalid query: unterminated string".to_string()),
+                    }
+                }
+                Token::Text(text)
+            }
+            c if c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | '*' | '?') => {
+                let mut word = c.to_string();
+                while let Some(next) = chars.next_if(|next| {
+                    next.is_alphanumeric() || matches!(next, '.' | '-' | '_' | '*' | '?')
+                }) {
+                    word.push(next);
+                }
+                Token::Word(word)
+            }
+            c => return Err(format!("Invalid query: unexpected character '{c}'")),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Word(word) => format!("'{word}'"),
+        Token::Text(text) => format!("\"{text}\""),
+        Token::Operator(_) => "operator".to_string(),
+        Token::OpenBracket => "'['".to_string(),
+        Token::CloseBracket => "']'".to_string(),
+        Token::OpenParen => "'('".to_string(),
+        Token::CloseParen => "')'".to_string(),
+        Token::Comma => "','".to_string(),
+    }
+}
+
+/// Recursive descent parser for filter queries
+struct QueryParser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// Number of currently open parentheses
+    depth: usize,
+}
+
+impl QueryParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token.ok_or_else(|| "Invalid query: unexpected end".to_string())
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next()? {
+            token if token == expected => Ok(()),
+            token => Err(format!(
+                "Invalid query: expected {}, found {}",
+                describe(&expected),
+                describe(&token)
+            )),
+        }
+    }
+
+    /// Consumes the keyword if it is next
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<WaypointFilter, String> {
+        let mut filter = self.parse_and()?;
+        while self.keyword("or") {
+            filter = filter.or(self.parse_and()?);
+        }
+        Ok(filter)
+    }
+
+    fn parse_and(&mut self) -> Result<WaypointFilter, String> {
+        let mut filter = self.parse_unary()?;
+        while self.keyword("and") {
+            filter = filter.and(self.parse_unary()?);
+        }
+        Ok(filter)
+    }
+
+    fn parse_unary(&mut self) -> Result<WaypointFilter, String> {
+        // Negations are counted instead of parsed recursively, so that a long
+        // run of `not` can't overflow the stack
+        let mut negations = 0;
+        while self.keyword("not") {
+            negations += 1;
+        }
+
+        let filter = self.parse_primary()?;
+        match negations % 2 {
+            0 => Ok(filter),
+            _ => Ok(!filter),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<WaypointFilter, String> {
+        if self.keyword("landable") {
+            return Ok(WaypointFilter::Landable);
+        }
+        if self.peek() == Some(&Token::OpenParen) {
+            if self.depth == MAX_DEPTH {
+                return Err("Invalid query: too deeply nested".to_string());
+            }
+            self.position += 1;
+            self.depth += 1;
+            let filter = self.parse_or()?;
+            self.depth -= 1;
+            self.expect(Token::CloseParen)?;
+            return Ok(filter);
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<WaypointFilter, String> {
+        let field = match self.next()? {
+            Token::Word(word) => word.to_lowercase(),
+            token => return Err(format!("Invalid query: unexpected {}", describe(&token))),
+        };
+
+        let (comparison, value) = if self.keyword("in") {
+            (Comparison::Equal, Value::List(self.parse_list()?))
+        } else {
+            match self.next()? {
+                Token::Operator(comparison) => (comparison, Value::Single(self.parse_value()?)),
+                token => {
+                    let found = describe(&token);
+                    return Err(format!("Invalid query: expected operator, found {found}"));
+                }
+            }
+        };
+
+        let values = match value {
+            Value::Single(value) => vec![value],
+            Value::List(values) => values,
+        };
+
+        let filter = match field.as_str() {
+            "elev" => {
+                let [value] = &values[..] else {
+                    return Err("Invalid query: 'elev' can't be compared to a list".to_string());
+                };
+                let elevation = Elevation::from_str(value)?;
+                return Ok(WaypointFilter::Elevation(comparison, elevation));
+            }
+            "style" => {
+                let styles = values
+                    .iter()
+                    .map(|value| parse_style(value))
+                    .collect::<Result<Vec<_>, _>>()?;
+                WaypointFilter::Style(styles)
+            }
+            "name" => any_of(values, WaypointFilter::Name),
+            "code" => any_of(values, WaypointFilter::Code),
+            "country" => any_of(values, WaypointFilter::Country),
+            "desc" => any_of(values, WaypointFilter::Description),
+            _ => return Err(format!("Invalid query: unknown field '{field}'")),
+        };
+
+        match comparison {
+            Comparison::Equal => Ok(filter),
+            Comparison::NotEqual => Ok(!filter),
+            _ => Err(format!(
+                "Invalid query: '{field}' only supports '=' and '!='"
+            )),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<String, String> {
+        match self.next()? {
+            Token::Word(value) | Token::Text(value) => Ok(value),
+            token => Err(format!(
+                "Invalid query: expected value, found {}",
+                describe(&token)
+            )),
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<Vec<String>, String> {
+        self.expect(Token::OpenBracket)?;
+        let mut values = Vec::new();
+        if self.peek() == Some(&Token::CloseBracket) {
+            self.position += 1;
+            return Ok(values);
+        }
+
+        loop {
+            values.push(self.parse_value()?);
+            match self.next()? {
+                Token::Comma => continue,
+                Token::CloseBracket => return Ok(values),
+                token => {
+                    let found = describe(&token);
+                    return Err(format!("Invalid query: expected ',' or ']', found {found}"));
+                }
+            }
+        }
+    }
+}
+
+fn any_of(values: Vec<String>, filter: fn(String) -> WaypointFilter) -> WaypointFilter {
+    let mut filters = values.into_iter().map(filter).collect::<Vec<_>>();
+    match filters.len() {
+        1 => filters.remove(0),
+        _ => WaypointFilter::Or(filters),
+    }
+}
+
+fn parse_style(value: &str) -> Result<WaypointStyle, String> {
+    parse_waypoint_style(value).ok_or_else(|| format!("Invalid query: unknown style '{value}'"))
+}
@@ -0,0 +1,465 @@
+//! Distance, bearing and destination computations
+//!
+//! Coordinates in CUP files use the WGS-1984 ellipsoid. The default
+//! [`EarthModel::Wgs84`] uses Vincenty's formulae on that ellipsoid, which are
+//! accurate to well below a millimetre. Vincenty's inverse formula does not
+//! converge for nearly antipodal points, so those are solved with Karney's
+//! approach of searching the initial azimuth of the geodesic instead, which
+//! keeps the same accuracy. [`EarthModel::Spherical`] uses the much cheaper
+//! great-circle formulae with an error of up to about 0.5%.
+//!
+//! ```
+//! use seeyou_cup::Coordinate;
+//! use seeyou_cup::geodesy::EarthModel;
+//!
+//! let lesce = Coordinate::new(46.35632, 14.17445).unwrap();
+//! let udbina = Coordinate::new(44.55778, 15.77444).unwrap();
+//!
+//! let distance = lesce.distance_to(&udbina);
+//! assert!((distance.to_meters() - 235_500.0).abs() < 500.0);
+//!
+//! let approximate = EarthModel::Spherical.distance(&lesce, &udbina);
+//! assert!((approximate.to_meters() - distance.to_meters()).abs() < 1000.0);
+//! ```
+
+use crate::{Coordinate, Distance, Waypoint};
+use std::f64::consts::PI;
+
+/// Semi-major axis of the WGS-84 ellipsoid in meters
+const WGS84_A: f64 = 6_378_137.0;
+/// Flattening of the WGS-84 ellipsoid
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+/// Mean earth radius in meters
+const MEAN_EARTH_RADIUS: f64 = 6_371_008.8;
+
+const MAX_ITERATIONS: usize = 200;
+/// Number of bisection steps of the azimuth search, enough to reach the
+/// resolution of `f64` on `[0, π]`
+const AZIMUTH_ITERATIONS: usize = 64;
+
+/// Earth model used for geodesic computations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EarthModel {
+    /// WGS-84 ellipsoid (Vincenty's formulae, with Karney's azimuth search for
+    /// nearly antipodal points)
+    #[default]
+    Wgs84,
+    /// Sphere with the mean earth radius (great-circle formulae)
+    Spherical,
+}
+
+impl EarthModel {
+    /// Shortest distance between two coordinates
+    pub fn distance(&self, from: &Coordinate, to: &Coordinate) -> Distance {
+        let (meters, _) = self.inverse(from.position(), to.position());
+        Distance::Meters(meters)
+    }
+
+    /// Initial bearing in degrees (0-360) of the shortest path between two coordinates
+    pub fn bearing(&self, from: &Coordinate, to: &Coordinate) -> f64 {
+        let (_, bearing) = self.inverse(from.position(), to.position());
+        bearing
+    }
+
+    /// Coordinate reached by travelling `distance` from `from` with the initial `bearing` in degrees
+    pub fn destination(&self, from: &Coordinate, bearing: f64, distance: &Distance) -> Coordinate {
+        let (lat, lon) = self.direct(from.position(), bearing, distance.to_meters());
+        Coordinate::new_unchecked(lat, lon)
+    }
+
+    /// Returns distance in meters and initial bearing in degrees between two
+    /// `(latitude, longitude)` pairs
+    pub(crate) fn inverse(&self, from: (f64, f64), to: (f64, f64)) -> (f64, f64) {
+        match self {
+            EarthModel::Wgs84 => {
+                vincenty_inverse(from, to).unwrap_or_else(|| antipodal_inverse(from, to))
+            }
+            EarthModel::Spherical => spherical_inverse(from, to),
+        }
+    }
+
+    /// Returns the distance in meters from `point` to the closest point of
+    /// the shortest path from `from` to `to`
+    ///
+    /// The cross-track distance is computed on the sphere, so the result
+    /// has the accuracy of [`EarthModel::Spherical`] near the path.
+    pub(crate) fn distance_to_segment(
+        &self,
+        point: (f64, f64),
+        from: (f64, f64),
+        to: (f64, f64),
+    ) -> f64 {
+        let (to_point, bearing_to_point) = self.inverse(from, point);
+        let (length, bearing) = self.inverse(from, to);
+
+        // The closest point is `from` if `point` lies behind it
+        let angle = (bearing_to_point - bearing).to_radians();
+        if angle.cos() <= 0.0 {
+            return to_point;
+        }
+
+        let angular = to_point / MEAN_EARTH_RADIUS;
+        let cross_track = (angular.sin() * angle.sin()).asin();
+        let along_track = (angular.cos() / cross_track.cos()).clamp(-1.0, 1.0).acos();
+        match along_track * MEAN_EARTH_RADIUS >= length {
+            true => self.inverse(to, point).0,
+            false => cross_track.abs() * MEAN_EARTH_RADIUS,
+        }
+    }
+
+    /// Returns the `(latitude, longitude)` pair reached from `from`
+    pub(crate) fn direct(&self, from: (f64, f64), bearing: f64, meters: f64) -> (f64, f64) {
+        match self {
+            EarthModel::Wgs84 => vincenty_direct(from, bearing, meters),
+            EarthModel::Spherical => spherical_direct(from, bearing, meters),
+        }
+    }
+}
+
+impl Coordinate {
+    /// Distance to `other` on the WGS-84 ellipsoid
+    pub fn distance_to(&self, other: &Coordinate) -> Distance {
+        EarthModel::Wgs84.distance(self, other)
+    }
+
+    /// Initial bearing in degrees (0-360) towards `other` on the WGS-84 ellipsoid
+    pub fn bearing_to(&self, other: &Coordinate) -> f64 {
+        EarthModel::Wgs84.bearing(self, other)
+    }
+
+    /// Coordinate reached by travelling `distance` with the initial `bearing` on the WGS-84 ellipsoid
+    pub fn destination(&self, bearing: f64, distance: &Distance) -> Coordinate {
+        EarthModel::Wgs84.destination(self, bearing, distance)
+    }
+}
+
+impl Waypoint {
+    /// Distance to `other` on the WGS-84 ellipsoid
+    pub fn distance_to(&self, other: &Waypoint) -> Distance {
+        let (meters, _) = EarthModel::Wgs84.inverse(self.position(), other.position());
+        Distance::Meters(meters)
+    }
+
+    /// Initial bearing in degrees (0-360) towards `other` on the WGS-84 ellipsoid
+    pub fn bearing_to(&self, other: &Waypoint) -> f64 {
+        let (_, bearing) = EarthModel::Wgs84.inverse(self.position(), other.position());
+        bearing
+    }
+
+    /// Coordinate reached by travelling `distance` with the initial `bearing` on the WGS-84 ellipsoid
+    pub fn destination(&self, bearing: f64, distance: &Distance) -> Coordinate {
+        let meters = distance.to_meters();
+        let (lat, lon) = EarthModel::Wgs84.direct(self.position(), bearing, meters);
+        Coordinate::new_unchecked(lat, lon)
+    }
+
+    pub(crate) fn position(&self) -> (f64, f64) {
+        (self.latitude, self.longitude)
+    }
+}
+
+fn normalize_bearing(degrees: f64) -> f64 {
+    degrees.rem_euclid(360.0)
+}
+
+fn normalize_longitude(degrees: f64) -> f64 {
+    let lon = (degrees + 180.0).rem_euclid(360.0) - 180.0;
+    if lon == -180.0 && degrees > 0.0 {
+        180.0
+    } else {
+        lon
+    }
+}
+
+fn spherical_inverse((lat1, lon1): (f64, f64), (lat2, lon2): (f64, f64)) -> (f64, f64) {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let delta_phi = phi2 - phi1;
+    let delta_lambda = (lon2 - lon1).to_radians();
+
+    let h = (delta_phi / 2.0).sin().powi(2)
+        + phi1.cos() * phi2.cos() * (delta_lambda / 2.0).sin().powi(2);
+    let distance = 2.0 * MEAN_EARTH_RADIUS * h.sqrt().min(1.0).asin();
+
+    let y = delta_lambda.sin() * phi2.cos();
+    let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * delta_lambda.cos();
+    let bearing = normalize_bearing(y.atan2(x).to_degrees());
+
+    (distance, bearing)
+}
+
+fn spherical_direct((lat, lon): (f64, f64), bearing: f64, meters: f64) -> (f64, f64) {
+    let phi1 = lat.to_radians();
+    let lambda1 = lon.to_radians();
+    let theta = bearing.to_radians();
+    let delta = meters / MEAN_EARTH_RADIUS;
+
+    let sin_phi2 = phi1.sin() * delta.cos() + phi1.cos() * delta.sin() * theta.cos();
+    let phi2 = sin_phi2.clamp(-1.0, 1.0).asin();
+    let y = theta.sin() * delta.sin() * phi1.cos();
+    let x = delta.cos() - phi1.sin() * sin_phi2;
+    let lambda2 = lambda1 + y.atan2(x);
+
+    (phi2.to_degrees(), normalize_longitude(lambda2.to_degrees()))
+}
+
+/// Returns Vincenty's `A` and `B` coefficients for `u²`
+fn vincenty_coefficients(u_sq: f64) -> (f64, f64) {
+    let a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+    let b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+    (a, b)
+}
+
+fn delta_sigma(big_b: f64, sin_sigma: f64, cos_sigma: f64, cos_2sigma_m: f64) -> f64 {
+    let cos_sq_2sigma_m = cos_2sigma_m * cos_2sigma_m;
+    big_b
+        * sin_sigma
+        * (cos_2sigma_m
+            + big_b / 4.0
+                * (cos_sigma * (-1.0 + 2.0 * cos_sq_2sigma_m)
+                    - big_b / 6.0
+                        * cos_2sigma_m
+                        * (-3.0 + 4.0 * sin_sigma * sin_sigma)
+                        * (-3.0 + 4.0 * cos_sq_2sigma_m)))
+}
+
+/// Difference between the longitude on the auxiliary sphere and on the ellipsoid
+fn longitude_correction(sin_alpha: f64, cos_sq_alpha: f64, sigma: f64, cos_2sigma_m: f64) -> f64 {
+    let c = WGS84_F / 16.0 * cos_sq_alpha * (4.0 + WGS84_F * (4.0 - 3.0 * cos_sq_alpha));
+    let (sin_sigma, cos_sigma) = sigma.sin_cos();
+    (1.0 - c)
+        * WGS84_F
+        * sin_alpha
+        * (sigma
+            + c * sin_sigma * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))))
+}
+
+/// Vincenty's inverse formula, returns `None` if the iteration does not
+/// converge (nearly antipodal points)
+fn vincenty_inverse((lat1, lon1): (f64, f64), (lat2, lon2): (f64, f64)) -> Option<(f64, f64)> {
+    let b = WGS84_A * (1.0 - WGS84_F);
+    let l = (lon2 - lon1).to_radians();
+    let u1 = ((1.0 - WGS84_F) * lat1.to_radians().tan()).atan();
+    let u2 = ((1.0 - WGS84_F) * lat2.to_radians().tan()).atan();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+
+    let mut lambda = l;
+    for _ in 0..MAX_ITERATIONS {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+            + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+        .sqrt();
+        if sin_sigma == 0.0 {
+            // Coincident points
+            return Some((0.0, 0.0));
+        }
+
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+        let cos_2sigma_m = if cos_sq_alpha != 0.0 {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_sq_alpha
+        } else {
+            // Equatorial line
+            0.0
+        };
+
+        let previous_lambda = lambda;
+        lambda = l + longitude_correction(sin_alpha, cos_sq_alpha, sigma, cos_2sigma_m);
+        if (lambda - previous_lambda).abs() > 1e-12 {
+            continue;
+        }
+
+        let u_sq = cos_sq_alpha * (WGS84_A.powi(2) - b.powi(2)) / b.powi(2);
+        let (big_a, big_b) = vincenty_coefficients(u_sq);
+        let delta_sigma = delta_sigma(big_b, sin_sigma, cos_sigma, cos_2sigma_m);
+        let distance = b * big_a * (sigma - delta_sigma);
+
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let alpha1 = (cos_u2 * sin_lambda).atan2(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+        return Some((distance, normalize_bearing(alpha1.to_degrees())));
+    }
+
+    None
+}
+
+/// Solves the inverse problem for nearly antipodal points, where Vincenty's
+/// iteration does not converge
+///
+/// Follows Karney, "Algorithms for geodesics" (2013): the points are first
+/// moved to the canonical configuration `lat1 <= 0`, `|lat2| <= |lat1|` and
+/// `0 <= lon12 <= 180°`, where the longitude reached at the latitude of the
+/// second point grows monotonically with the initial azimuth. That azimuth is
+/// then found by bisection, using Vincenty's series for the longitude and
+/// distance on the ellipsoid.
+fn antipodal_inverse((lat1, lon1): (f64, f64), (lat2, lon2): (f64, f64)) -> (f64, f64) {
+    let lon12 = normalize_longitude(lon2 - lon1);
+    let mut lon_sign = if lon12 < 0.0 { -1.0 } else { 1.0 };
+    let lon12 = lon12.abs().to_radians();
+
+    let swapped = lat1.abs() < lat2.abs();
+    let (lat1, lat2) = match swapped {
+        true => {
+            lon_sign = -lon_sign;
+            (lat2, lat1)
+        }
+        false => (lat1, lat2),
+    };
+    let lat_sign = if lat1 > 0.0 { -1.0 } else { 1.0 };
+    let beta1 = reduced_latitude(lat1 * lat_sign);
+    let beta2 = reduced_latitude(lat2 * lat_sign);
+
+    let (mut low, mut high) = (0.0, PI);
+    let mut geodesic = azimuth_geodesic(beta1, beta2, high);
+    for _ in 0..AZIMUTH_ITERATIONS {
+        let alpha1 = (low + high) / 2.0;
+        let candidate = azimuth_geodesic(beta1, beta2, alpha1);
+        match candidate.lon12 < lon12 {
+            true => low = alpha1,
+            false => {
+                high = alpha1;
+                geodesic = candidate;
+            }
+        }
+    }
+
+    // Undo the canonical configuration, the azimuth at the first point is
+    // the reversed azimuth at the end of the swapped geodesic
+    let (sin_alpha, cos_alpha) = match swapped {
+        true => (-geodesic.sin_alpha2, -geodesic.cos_alpha2),
+        false => geodesic.alpha1.sin_cos(),
+    };
+    let bearing = (sin_alpha * lon_sign).atan2(cos_alpha * lat_sign);
+    (geodesic.distance, normalize_bearing(bearing.to_degrees()))
+}
+
+/// Sine and cosine of the reduced latitude
+fn reduced_latitude(lat: f64) -> (f64, f64) {
+    let (sin_phi, cos_phi) = lat.to_radians().sin_cos();
+    let (sin_beta, cos_beta) = ((1.0 - WGS84_F) * sin_phi, cos_phi);
+    let norm = sin_beta.hypot(cos_beta);
+    (sin_beta / norm, (cos_beta / norm).max(f64::MIN_POSITIVE))
+}
+
+/// Geodesic from the first point to the latitude of the second point
+struct AzimuthGeodesic {
+    alpha1: f64,
+    sin_alpha2: f64,
+    cos_alpha2: f64,
+    /// Longitude difference in radians
+    lon12: f64,
+    /// Length in meters
+    distance: f64,
+}
+
+/// Follows the geodesic leaving the reduced latitude `beta1` with the
+/// azimuth `alpha1` until it reaches `beta2` heading north
+fn azimuth_geodesic(
+    (sin_beta1, cos_beta1): (f64, f64),
+    (sin_beta2, cos_beta2): (f64, f64),
+    alpha1: f64,
+) -> AzimuthGeodesic {
+    let b = WGS84_A * (1.0 - WGS84_F);
+    let (sin_alpha1, mut cos_alpha1) = alpha1.sin_cos();
+    if sin_beta1 == 0.0 && cos_alpha1 == 0.0 {
+        // Heading east on the equator, move slightly south
+        cos_alpha1 = -f64::MIN_POSITIVE;
+    }
+
+    // Azimuth at the equator crossing
+    let sin_alpha0 = sin_alpha1 * cos_beta1;
+    let cos_alpha0 = cos_alpha1.hypot(sin_alpha1 * sin_beta1);
+
+    let sin_alpha2 = if cos_beta2 != cos_beta1 {
+        sin_alpha0 / cos_beta2
+    } else {
+        sin_alpha1
+    };
+    let cos_alpha2 = if cos_beta2 != cos_beta1 || sin_beta2.abs() != -sin_beta1 {
+        let cos_sq_difference = match cos_beta1 < -sin_beta1 {
+            true => (cos_beta2 - cos_beta1) * (cos_beta2 + cos_beta1),
+            false => (sin_beta1 - sin_beta2) * (sin_beta1 + sin_beta2),
+        };
+        ((cos_alpha1 * cos_beta1).powi(2) + cos_sq_difference).sqrt() / cos_beta2
+    } else {
+        cos_alpha1.abs()
+    };
+
+    // Arc lengths and longitudes on the auxiliary sphere, measured from the
+    // equator crossing
+    let (sin_sigma1, cos_sigma1) = normalize(sin_beta1, cos_alpha1 * cos_beta1);
+    let (sin_sigma2, cos_sigma2) = normalize(sin_beta2, cos_alpha2 * cos_beta2);
+    // Both differences are in `[0, π]`, `abs()` also turns `-0.0` into `0.0`
+    let sigma12 = (cos_sigma1 * sin_sigma2 - sin_sigma1 * cos_sigma2)
+        .max(0.0)
+        .abs()
+        .atan2(cos_sigma1 * cos_sigma2 + sin_sigma1 * sin_sigma2);
+
+    let (sin_omega1, cos_omega1) = (sin_alpha0 * sin_beta1, cos_alpha1 * cos_beta1);
+    let (sin_omega2, cos_omega2) = (sin_alpha0 * sin_beta2, cos_alpha2 * cos_beta2);
+    let omega12 = (cos_omega1 * sin_omega2 - sin_omega1 * cos_omega2)
+        .max(0.0)
+        .abs()
+        .atan2(cos_omega1 * cos_omega2 + sin_omega1 * sin_omega2);
+
+    let cos_sq_alpha0 = cos_alpha0 * cos_alpha0;
+    let cos_2sigma_m = cos_sigma1 * cos_sigma2 - sin_sigma1 * sin_sigma2;
+    let lon12 = omega12 - longitude_correction(sin_alpha0, cos_sq_alpha0, sigma12, cos_2sigma_m);
+
+    let u_sq = cos_sq_alpha0 * (WGS84_A.powi(2) - b.powi(2)) / b.powi(2);
+    let (big_a, big_b) = vincenty_coefficients(u_sq);
+    let (sin_sigma12, cos_sigma12) = sigma12.sin_cos();
+    let delta_sigma = delta_sigma(big_b, sin_sigma12, cos_sigma12, cos_2sigma_m);
+
+    AzimuthGeodesic {
+        alpha1,
+        sin_alpha2,
+        cos_alpha2,
+        lon12,
+        distance: b * big_a * (sigma12 - delta_sigma),
+    }
+}
+
+fn normalize(sin: f64, cos: f64) -> (f64, f64) {
+    let norm = sin.hypot(cos);
+    (sin / norm, cos / norm)
+}
+
+/// Vincenty's direct formula
+fn vincenty_direct((lat, lon): (f64, f64), bearing: f64, meters: f64) -> (f64, f64) {
+    let b = WGS84_A * (1.0 - WGS84_F);
+    let (sin_alpha1, cos_alpha1) = bearing.to_radians().sin_cos();
+
+    let tan_u1 = (1.0 - WGS84_F) * lat.to_radians().tan();
+    let cos_u1 = 1.0 / (1.0 + tan_u1 * tan_u1).sqrt();
+    let sin_u1 = tan_u1 * cos_u1;
+    let sigma1 = tan_u1.atan2(cos_alpha1);
+    let sin_alpha = cos_u1 * sin_alpha1;
+    let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+    let u_sq = cos_sq_alpha * (WGS84_A.powi(2) - b.powi(2)) / b.powi(2);
+    let (big_a, big_b) = vincenty_coefficients(u_sq);
+
+    let initial_sigma = meters / (b * big_a);
+    let mut sigma = initial_sigma;
+    for _ in 0..MAX_ITERATIONS {
+        let cos_2sigma_m = (2.0 * sigma1 + sigma).cos();
+        let (sin_sigma, cos_sigma) = sigma.sin_cos();
+        let previous_sigma = sigma;
+        sigma = initial_sigma + delta_sigma(big_b, sin_sigma, cos_sigma, cos_2sigma_m);
+        if (sigma - previous_sigma).abs() < 1e-12 {
+            break;
+        }
+    }
+
+    let (sin_sigma, cos_sigma) = sigma.sin_cos();
+    let cos_2sigma_m = (2.0 * sigma1 + sigma).cos();
+    let tmp = sin_u1 * sin_sigma - cos_u1 * cos_sigma * cos_alpha1;
+    let phi2 = (sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_alpha1)
+        .atan2((1.0 - WGS84_F) * (sin_alpha * sin_alpha + tmp * tmp).sqrt());
+    let lambda =
+        (sin_sigma * sin_alpha1).atan2(cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_alpha1);
+    let l = lambda - longitude_correction(sin_alpha, cos_sq_alpha, sigma, cos_2sigma_m);
+
+    (phi2.to_degrees(), normalize_longitude(lon + l.to_degrees()))
+}
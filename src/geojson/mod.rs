@@ -0,0 +1,82 @@
+mod read;
+mod write;
+
+use crate::{CupFile, Error, Warning};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Configuration for [`CupFile::to_geojson_writer()`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeoJsonOptions {
+    /// Whether to add a feature with the outline of every observation
+    /// zone, a `Polygon` for areas and a `LineString` for lines
+    pub observation_zones: bool,
+}
+
+impl CupFile {
+    /// Reads a GeoJSON `FeatureCollection` in the schema written by
+    /// [`CupFile::to_geojson_writer()`]
+    ///
+    /// `Point` features become waypoints and `LineString` features with a
+    /// `waypoint_names` property become tasks, unless their `kind` property
+    /// says otherwise. The position of a waypoint is always taken from its
+    /// geometry, so that points moved in a GIS tool keep their new
+    /// position. The same goes for the elevation: the `elevation` property
+    /// only keeps its unit if it matches the z coordinate, and is ignored
+    /// with a warning otherwise. Missing properties get their default
+    /// values, and other features, like observation zones, are ignored.
+    ///
+    /// Features without a name or a valid position and invalid properties
+    /// are skipped with a warning.
+    pub fn from_geojson_reader<R: Read>(mut reader: R) -> Result<(Self, Vec<Warning>), Error> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        Self::from_geojson_str(&content)
+    }
+
+    pub fn from_geojson_path<P: AsRef<Path>>(path: P) -> Result<(Self, Vec<Warning>), Error> {
+        let file = File::open(path)?;
+        Self::from_geojson_reader(file)
+    }
+
+    pub fn from_geojson_str(s: &str) -> Result<(Self, Vec<Warning>), Error> {
+        read::parse(s)
+    }
+
+    /// Writes the file as a GeoJSON `FeatureCollection`
+    ///
+    /// Every waypoint becomes a `Point` feature with its elevation in
+    /// meters as third coordinate and all other [`Waypoint`](crate::Waypoint)
+    /// fields as properties, with `kind` set to `waypoint`. Every task
+    /// becomes a `LineString` feature from start to finish, with `kind` set
+    /// to `task` and all [`Task`](crate::Task) fields as properties. Inline
+    /// task points are nested as `Point` features. With
+    /// [`GeoJsonOptions::observation_zones`], an `observation_zone` feature
+    /// is added for every zone with a radius. Polygon rings are
+    /// counterclockwise as required by RFC 7946.
+    ///
+    /// Fails if a task refers to a waypoint that isn't defined.
+    pub fn to_geojson_writer<W: Write>(
+        &self,
+        mut writer: W,
+        options: &GeoJsonOptions,
+    ) -> Result<(), Error> {
+        writer.write_all(self.to_geojson_string(options)?.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn to_geojson_path<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: &GeoJsonOptions,
+    ) -> Result<(), Error> {
+        let file = File::create(path)?;
+        self.to_geojson_writer(file, options)
+    }
+
+    pub fn to_geojson_string(&self, options: &GeoJsonOptions) -> Result<String, Error> {
+        let collection = write::feature_collection(self, options)?;
+        Ok(serde_json::to_string_pretty(&collection)?)
+    }
+}
@@ -0,0 +1,226 @@
+use crate::error::ParseIssue;
+use crate::{
+    Column, ColumnLayout, Coordinate, CupFile, Elevation, Error, FromStr, ObsZoneStyle,
+    ObservationZone, Task, TaskOptions, Warning, Waypoint, WaypointStyle,
+};
+use serde_json::Value;
+use std::fmt::Display;
+
+/// Largest difference in meters between the `elevation` property and the z
+/// coordinate of a waypoint that is attributed to unit conversion
+const ELEVATION_TOLERANCE: f64 = 0.5;
+
+pub fn parse(s: &str) -> Result<(CupFile, Vec<Warning>), Error> {
+    let collection: Value = serde_json::from_str(s)?;
+    if collection["type"] != "FeatureCollection" {
+        return Err(ParseIssue::new("Expected a GeoJSON FeatureCollection").into());
+    }
+
+    let mut warnings = Vec::new();
+    let mut cup_file = CupFile::default();
+
+    if let Some(columns) = collection["columns"].as_array() {
+        let columns = columns.iter().filter_map(Value::as_str);
+        cup_file.columns = ColumnLayout::new(columns.filter_map(Column::from_header));
+    }
+
+    let features = collection["features"].as_array();
+    for (index, feature) in features.into_iter().flatten().enumerate() {
+        let mut issues = Issues {
+            feature: index,
+            warnings: &mut warnings,
+        };
+
+        let properties = &feature["properties"];
+        let kind = properties["kind"].as_str();
+        let kind = kind.or(match feature["geometry"]["type"].as_str() {
+            Some("Point") => Some("waypoint"),
+            Some("LineString") if properties["waypoint_names"].is_array() => Some("task"),
+            _ => None,
+        });
+
+        match kind {
+            Some("waypoint") => cup_file
+                .waypoints
+                .extend(parse_waypoint(feature, &mut issues)),
+            Some("task") => cup_file.tasks.push(parse_task(properties, &mut issues)),
+            _ => {}
+        }
+    }
+
+    Ok((cup_file, warnings))
+}
+
+fn parse_waypoint(feature: &Value, issues: &mut Issues) -> Option<Waypoint> {
+    let properties = &feature["properties"];
+    let name = issues.string(properties, "name");
+    if name.is_empty() {
+        issues.warn("Name field cannot be empty");
+        return None;
+    }
+
+    let coordinates = &feature["geometry"]["coordinates"];
+    let position = coordinates[1].as_f64().zip(coordinates[0].as_f64());
+    let coordinate = position.and_then(|(lat, lon)| Coordinate::new(lat, lon).ok());
+    let Some(coordinate) = coordinate else {
+        issues.warn(format!("Invalid position: '{name}'"));
+        return None;
+    };
+
+    // The z coordinate wins over the property, which only keeps its unit
+    // as long as both agree
+    let property = issues.parse::<Elevation>(properties, "elevation");
+    let elevation = match (coordinates[2].as_f64(), property) {
+        (Some(z), Some(property)) if (property.to_meters() - z).abs() > ELEVATION_TOLERANCE => {
+            issues.warn(format!(
+                "Ignored field: Elevation '{property}' differs from z coordinate: '{z}'"
+            ));
+            Some(Elevation::Meters(z))
+        }
+        (z, property) => property.or(z.map(Elevation::Meters)),
+    };
+
+    let style = issues.parse::<u8>(properties, "style").and_then(|style| {
+        let parsed = WaypointStyle::ALL.get(style as usize).copied();
+        if parsed.is_none() {
+            issues.warn(format!("Ignored field: Unknown waypoint style: '{style}'"));
+        }
+        parsed
+    });
+
+    Some(Waypoint {
+        name,
+        code: issues.string(properties, "code"),
+        country: issues.string(properties, "country"),
+        latitude: coordinate.latitude(),
+        longitude: coordinate.longitude(),
+        elevation: elevation.unwrap_or(Elevation::Meters(0.0)),
+        style: style.unwrap_or(WaypointStyle::Waypoint),
+        runway_direction: issues.parse(properties, "runway_direction"),
+        runway_length: issues.parse(properties, "runway_length"),
+        runway_width: issues.parse(properties, "runway_width"),
+        frequency: issues.string(properties, "frequency"),
+        description: issues.string(properties, "description"),
+        userdata: issues.string(properties, "userdata"),
+        pictures: issues.strings(properties, "pictures"),
+    })
+}
+
+fn parse_task(properties: &Value, issues: &mut Issues) -> Task {
+    let description = issues.string(properties, "description");
+
+    let options = &properties["options"];
+    let options = options.is_object().then(|| TaskOptions {
+        no_start: Some(issues.string(options, "no_start")).filter(|s| !s.is_empty()),
+        task_time: Some(issues.string(options, "task_time")).filter(|s| !s.is_empty()),
+        wp_dis: issues.parse(options, "wp_dis"),
+        near_dis: issues.parse(options, "near_dis"),
+        near_alt: issues.parse(options, "near_alt"),
+        min_dis: issues.parse(options, "min_dis"),
+        random_order: issues.parse(options, "random_order"),
+        max_pts: issues.parse(options, "max_pts"),
+        before_pts: issues.parse(options, "before_pts"),
+        after_pts: issues.parse(options, "after_pts"),
+        bonus: issues.parse(options, "bonus"),
+    });
+
+    let mut observation_zones = Vec::new();
+    for zone in properties["observation_zones"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        let index = issues.parse(zone, "index");
+        let style = issues.parse::<u8>(zone, "style");
+        let Some((index, style)) = index.zip(style.and_then(ObsZoneStyle::from_u8)) else {
+            issues.warn("Ignored observation zone: Missing ObsZone index or style");
+            continue;
+        };
+
+        observation_zones.push(ObservationZone {
+            index,
+            style,
+            r1: issues.parse(zone, "r1"),
+            a1: issues.parse(zone, "a1"),
+            r2: issues.parse(zone, "r2"),
+            a2: issues.parse(zone, "a2"),
+            a12: issues.parse(zone, "a12"),
+            line: issues.parse(zone, "line"),
+        });
+    }
+
+    let mut points = Vec::new();
+    for point in properties["points"].as_array().into_iter().flatten() {
+        let index = issues.parse(point, "index");
+        let waypoint = parse_waypoint(&point["waypoint"], issues);
+        if let Some(point) = index.zip(waypoint) {
+            points.push(point);
+        }
+    }
+
+    Task {
+        description: Some(description).filter(|s| !s.is_empty()),
+        waypoint_names: issues.strings(properties, "waypoint_names"),
+        options,
+        observation_zones,
+        points,
+        multiple_starts: issues.strings(properties, "multiple_starts"),
+    }
+}
+
+/// Collects the warnings of a feature
+struct Issues<'a> {
+    /// Index of the feature in the collection
+    feature: usize,
+    warnings: &'a mut Vec<Warning>,
+}
+
+impl Issues<'_> {
+    fn warn(&mut self, message: impl Display) {
+        let message = format!("Feature {}: {message}", self.feature);
+        self.warnings.push(ParseIssue::new(message).into());
+    }
+
+    /// String property, or an empty string if it's missing
+    fn string(&mut self, object: &Value, key: &str) -> String {
+        match &object[key] {
+            Value::Null => String::new(),
+            Value::String(value) => value.clone(),
+            value => {
+                self.warn(format!("Ignored field: Invalid {key}: '{value}'"));
+                String::new()
+            }
+        }
+    }
+
+    /// Array property of strings, or an empty list if it's missing
+    fn strings(&mut self, object: &Value, key: &str) -> Vec<String> {
+        match &object[key] {
+            Value::Null => Vec::new(),
+            Value::Array(values) if values.iter().all(Value::is_string) => values
+                .iter()
+                .filter_map(|value| value.as_str().map(ToString::to_string))
+                .collect(),
+            value => {
+                self.warn(format!("Ignored field: Invalid {key}: '{value}'"));
+                Vec::new()
+            }
+        }
+    }
+
+    /// Parses a string, number or boolean property, or returns `None` if
+    /// it's missing or invalid
+    fn parse<T: FromStr>(&mut self, object: &Value, key: &str) -> Option<T> {
+        let value = match &object[key] {
+            Value::Null => return None,
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        };
+
+        let parsed = value.trim().parse().ok();
+        if parsed.is_none() {
+            self.warn(format!("Ignored field: Invalid {key}: '{value}'"));
+        }
+        parsed
+    }
+}
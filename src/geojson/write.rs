@@ -0,0 +1,150 @@
+use crate::geojson::GeoJsonOptions;
+use crate::task::TaskPoint;
+use crate::{Column, CupFile, Error, ObservationZone, Task, TaskOptions, Waypoint, ZoneGeometry};
+use serde_json::{Value, json};
+
+pub fn feature_collection(cup_file: &CupFile, options: &GeoJsonOptions) -> Result<Value, Error> {
+    let mut features = cup_file
+        .waypoints
+        .iter()
+        .map(waypoint_feature)
+        .collect::<Vec<_>>();
+
+    for (index, task) in cup_file.tasks.iter().enumerate() {
+        let points = task.resolve(&cup_file.waypoints)?;
+        features.push(task_feature(task, &points));
+        if options.observation_zones {
+            features.extend(zone_features(index, &points));
+        }
+    }
+
+    let columns = cup_file.columns.columns().iter().map(Column::header);
+    Ok(json!({
+        "type": "FeatureCollection",
+        "columns": columns.collect::<Vec<_>>(),
+        "features": features,
+    }))
+}
+
+fn waypoint_feature(waypoint: &Waypoint) -> Value {
+    let coordinates = [
+        waypoint.longitude,
+        waypoint.latitude,
+        waypoint.elevation.to_meters(),
+    ];
+    json!({
+        "type": "Feature",
+        "geometry": {
+            "type": "Point",
+            "coordinates": coordinates,
+        },
+        "properties": {
+            "kind": "waypoint",
+            "name": waypoint.name,
+            "code": waypoint.code,
+            "country": waypoint.country,
+            "elevation": waypoint.elevation.to_string(),
+            "style": waypoint.style as u8,
+            "runway_direction": waypoint.runway_direction,
+            "runway_length": waypoint.runway_length.as_ref().map(ToString::to_string),
+            "runway_width": waypoint.runway_width.as_ref().map(ToString::to_string),
+            "frequency": waypoint.frequency,
+            "description": waypoint.description,
+            "userdata": waypoint.userdata,
+            "pictures": waypoint.pictures,
+        },
+    })
+}
+
+fn task_feature(task: &Task, points: &[TaskPoint<'_>]) -> Value {
+    // A line string needs at least two positions
+    let coordinates = points
+        .iter()
+        .map(|point| [point.waypoint.longitude, point.waypoint.latitude])
+        .collect::<Vec<_>>();
+    let geometry = match coordinates.len() {
+        0 | 1 => Value::Null,
+        _ => json!({ "type": "LineString", "coordinates": coordinates }),
+    };
+
+    let inline = task
+        .points
+        .iter()
+        .map(|(index, waypoint)| json!({ "index": index, "waypoint": waypoint_feature(waypoint) }));
+    let zones = task.observation_zones.iter().map(observation_zone);
+
+    json!({
+        "type": "Feature",
+        "geometry": geometry,
+        "properties": {
+            "kind": "task",
+            "description": task.description,
+            "waypoint_names": task.waypoint_names,
+            "options": task.options.as_ref().map(task_options),
+            "observation_zones": zones.collect::<Vec<_>>(),
+            "points": inline.collect::<Vec<_>>(),
+            "multiple_starts": task.multiple_starts,
+        },
+    })
+}
+
+fn task_options(options: &TaskOptions) -> Value {
+    json!({
+        "no_start": options.no_start,
+        "task_time": options.task_time,
+        "wp_dis": options.wp_dis,
+        "near_dis": options.near_dis.as_ref().map(ToString::to_string),
+        "near_alt": options.near_alt.as_ref().map(ToString::to_string),
+        "min_dis": options.min_dis,
+        "random_order": options.random_order,
+        "max_pts": options.max_pts,
+        "before_pts": options.before_pts,
+        "after_pts": options.after_pts,
+        "bonus": options.bonus,
+    })
+}
+
+fn observation_zone(zone: &ObservationZone) -> Value {
+    json!({
+        "index": zone.index,
+        "style": zone.style as u8,
+        "r1": zone.r1.as_ref().map(ToString::to_string),
+        "a1": zone.a1,
+        "r2": zone.r2.as_ref().map(ToString::to_string),
+        "a2": zone.a2,
+        "a12": zone.a12,
+        "line": zone.line,
+    })
+}
+
+fn zone_features(task: usize, points: &[TaskPoint<'_>]) -> Vec<Value> {
+    let zones = ZoneGeometry::for_task(points);
+    let zones = points.iter().zip(zones);
+    // Zones without a radius have no outline
+    let zones = zones.filter(|(point, _)| point.observation_zone.is_some_and(|z| z.r1.is_some()));
+
+    zones
+        .map(|(point, zone)| {
+            let outline = zone
+                .polygon()
+                .iter()
+                .map(|coordinate| [coordinate.longitude(), coordinate.latitude()])
+                .collect::<Vec<_>>();
+            let geometry = match zone.is_line() {
+                true => json!({ "type": "LineString", "coordinates": outline }),
+                false => json!({ "type": "Polygon", "coordinates": [outline] }),
+            };
+
+            json!({
+                "type": "Feature",
+                "geometry": geometry,
+                "properties": {
+                    "kind": "observation_zone",
+                    "task": task,
+                    "index": point.index,
+                    "name": point.waypoint.name,
+                },
+            })
+        })
+        .collect()
+}
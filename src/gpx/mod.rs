@@ -0,0 +1,90 @@
+mod read;
+mod write;
+
+use crate::{CupFile, Error, Warning, WaypointStyle};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Namespace of the GPX 1.1 schema
+const GPX_NAMESPACE: &str = "http://www.topografix.com/GPX/1/1";
+/// Namespace of the `<extensions>` elements that keep the CUP fields without
+/// a GPX equivalent
+pub const GPX_EXTENSION_NAMESPACE: &str = "https://github.com/glide-rs/seeyou-cup/gpx/1";
+
+impl CupFile {
+    /// Reads a GPX file
+    ///
+    /// Waypoints are read from the `<wpt>` elements and tasks from the
+    /// `<rte>` elements. The CUP fields written to the `<extensions>` by
+    /// [`CupFile::to_gpx_writer()`] are restored. Without them, the style
+    /// is guessed from the `<type>` and `<sym>` elements, and a route
+    /// point refers to the waypoint with the same name or is defined
+    /// inline if there is none.
+    ///
+    /// Waypoints and route points without a name or a valid position are
+    /// skipped with a warning.
+    pub fn from_gpx_reader<R: Read>(mut reader: R) -> Result<(Self, Vec<Warning>), Error> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        Self::from_gpx_str(&content)
+    }
+
+    pub fn from_gpx_path<P: AsRef<Path>>(path: P) -> Result<(Self, Vec<Warning>), Error> {
+        let file = File::open(path)?;
+        Self::from_gpx_reader(file)
+    }
+
+    pub fn from_gpx_str(s: &str) -> Result<(Self, Vec<Warning>), Error> {
+        read::parse(s)
+    }
+
+    /// Writes the file as GPX 1.1
+    ///
+    /// Every waypoint becomes a `<wpt>` with its name, elevation,
+    /// description, and a `<sym>` and `<type>` for its style. Every task
+    /// becomes a `<rte>` with a `<rtept>` for each point from start to
+    /// finish. All other fields, the takeoff and landing, the task
+    /// options, observation zones and alternative start points are kept in
+    /// `<extensions>` in the [`GPX_EXTENSION_NAMESPACE`], so that the file
+    /// can be read back without losses. Inline takeoff and landing points
+    /// are only kept by name.
+    ///
+    /// Fails if a task refers to a waypoint that isn't defined.
+    pub fn to_gpx_writer<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        writer.write_all(self.to_gpx_string()?.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn to_gpx_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let file = File::create(path)?;
+        self.to_gpx_writer(file)
+    }
+
+    pub fn to_gpx_string(&self) -> Result<String, Error> {
+        write::format(self)
+    }
+}
+
+/// Garmin symbol name for a waypoint style
+fn symbol(style: WaypointStyle) -> &'static str {
+    match style {
+        WaypointStyle::Unknown | WaypointStyle::Waypoint => "Waypoint",
+        WaypointStyle::GrassAirfield => "Soft Field",
+        WaypointStyle::Outlanding => "Private Field",
+        WaypointStyle::GlidingAirfield => "Glider Area",
+        WaypointStyle::SolidAirfield => "Airport",
+        WaypointStyle::MountainPass => "Scenic Area",
+        WaypointStyle::MountainTop => "Summit",
+        WaypointStyle::TransmitterMast => "Tall Tower",
+        WaypointStyle::Vor | WaypointStyle::Ndb => "Radio Beacon",
+        WaypointStyle::CoolingTower => "Short Tower",
+        WaypointStyle::Dam => "Dam",
+        WaypointStyle::Tunnel => "Tunnel",
+        WaypointStyle::Bridge => "Bridge",
+        WaypointStyle::PowerPlant | WaypointStyle::Castle => "Building",
+        WaypointStyle::Intersection => "Crossing",
+        WaypointStyle::Marker | WaypointStyle::ControlPoint => "Flag",
+        WaypointStyle::PgTakeOff | WaypointStyle::PgLandingZone => "Parachute Area",
+    }
+}
@@ -0,0 +1,261 @@
+use crate::error::ParseIssue;
+use crate::gpx::{GPX_EXTENSION_NAMESPACE, symbol};
+use crate::parser::parse_waypoint_style;
+use crate::{
+    Column, ColumnLayout, Coordinate, CupFile, Elevation, Error, FromStr, ObsZoneStyle,
+    ObservationZone, Task, TaskOptions, Warning, Waypoint, WaypointStyle,
+};
+use roxmltree::{Document, Node};
+
+pub fn parse(s: &str) -> Result<(CupFile, Vec<Warning>), Error> {
+    let document = Document::parse(s)?;
+    let root = document.root_element();
+    if root.tag_name().name() != "gpx" {
+        return Err(ParseIssue::new("Missing <gpx> root element").into());
+    }
+
+    let mut warnings = Vec::new();
+    let mut cup_file = CupFile::default();
+
+    let metadata = children(root, "metadata").next();
+    let columns = metadata.and_then(|metadata| extension(metadata, "columns"));
+    if let Some(columns) = columns {
+        let columns = text(columns).split(',').filter_map(Column::from_header);
+        cup_file.columns = ColumnLayout::new(columns);
+    }
+
+    for node in children(root, "wpt") {
+        if let Some(waypoint) = parse_point(node, &mut warnings) {
+            cup_file.waypoints.push(waypoint);
+        }
+    }
+
+    for node in children(root, "rte") {
+        let task = parse_route(node, &cup_file.waypoints, &mut warnings);
+        cup_file.tasks.push(task);
+    }
+
+    Ok((cup_file, warnings))
+}
+
+fn parse_point(node: Node, warnings: &mut Vec<Warning>) -> Option<Waypoint> {
+    let name = children(node, "name").next().map(text).unwrap_or_default();
+    if name.is_empty() {
+        warnings.push(warning(node, "Name field cannot be empty"));
+        return None;
+    }
+
+    let latitude = node
+        .attribute("lat")
+        .and_then(|lat| lat.trim().parse().ok());
+    let longitude = node
+        .attribute("lon")
+        .and_then(|lon| lon.trim().parse().ok());
+    let coordinate = latitude
+        .zip(longitude)
+        .and_then(|(latitude, longitude)| Coordinate::new(latitude, longitude).ok());
+    let Some(coordinate) = coordinate else {
+        warnings.push(warning(node, format!("Invalid position: '{name}'")));
+        return None;
+    };
+
+    let elevation = parse_extension::<Elevation>(node, "elev", warnings).or_else(|| {
+        let ele = children(node, "ele").next()?;
+        parse_value::<f64>(ele, "elevation", text(ele), warnings).map(Elevation::Meters)
+    });
+
+    let field = |column: Column| extension(node, column.header()).map(text);
+    let style = extension(node, Column::Style.header()).and_then(|style| {
+        let parsed = parse_waypoint_style(text(style));
+        if parsed.is_none() {
+            let message = format!("Ignored field: Unknown waypoint style: '{}'", text(style));
+            warnings.push(warning(style, message));
+        }
+        parsed
+    });
+    let style = style.or_else(|| guess_style(node));
+
+    let waypoint = Waypoint {
+        name: name.to_string(),
+        code: field(Column::Code).unwrap_or_default().to_string(),
+        country: field(Column::Country).unwrap_or_default().to_string(),
+        latitude: coordinate.latitude(),
+        longitude: coordinate.longitude(),
+        elevation: elevation.unwrap_or(Elevation::Meters(0.0)),
+        style: style.unwrap_or(WaypointStyle::Waypoint),
+        runway_direction: parse_extension(node, Column::RwDir.header(), warnings),
+        runway_length: parse_extension(node, Column::RwLen.header(), warnings),
+        runway_width: parse_extension(node, Column::RwWidth.header(), warnings),
+        frequency: field(Column::Freq).unwrap_or_default().to_string(),
+        description: children(node, "desc")
+            .next()
+            .map(text)
+            .unwrap_or_default()
+            .to_string(),
+        userdata: field(Column::Userdata).unwrap_or_default().to_string(),
+        pictures: extensions(node, "pic")
+            .map(|pic| text(pic).to_string())
+            .collect(),
+    };
+    Some(waypoint)
+}
+
+/// Guesses the waypoint style from the `<type>` or `<sym>` element
+fn guess_style(node: Node) -> Option<WaypointStyle> {
+    let matching = |tag: &str, name: fn(WaypointStyle) -> &'static str| {
+        let value = text(children(node, tag).next()?);
+        let mut styles = WaypointStyle::ALL.into_iter();
+        styles.find(|style| name(*style).eq_ignore_ascii_case(value))
+    };
+    matching("type", |style| style.description()).or_else(|| matching("sym", symbol))
+}
+
+fn parse_route(node: Node, waypoints: &[Waypoint], warnings: &mut Vec<Warning>) -> Task {
+    let mut task = Task {
+        description: children(node, "name")
+            .next()
+            .map(|name| text(name).to_string()),
+        waypoint_names: Vec::new(),
+        options: None,
+        observation_zones: Vec::new(),
+        points: Vec::new(),
+        multiple_starts: Vec::new(),
+    };
+
+    let mut course = Vec::new();
+    for point in children(node, "rtept") {
+        let Some(waypoint) = parse_point(point, warnings) else {
+            continue;
+        };
+
+        let inline = match extension(point, "inline") {
+            Some(_) => true,
+            None => !waypoints.iter().any(|w| w.name == waypoint.name),
+        };
+        course.push(waypoint.name.clone());
+        if inline {
+            // `Point=` indices count from the takeoff
+            task.points.push((course.len() as u32, waypoint));
+        }
+    }
+
+    // Without the takeoff and landing in the extensions, the task starts
+    // and ends at the first and last point
+    let takeoff = extension(node, "takeoff")
+        .map(text)
+        .map(ToString::to_string);
+    let landing = extension(node, "landing")
+        .map(text)
+        .map(ToString::to_string);
+    let has_extensions = takeoff.is_some() || landing.is_some();
+    let takeoff = takeoff.or_else(|| course.first().filter(|_| !has_extensions).cloned());
+    let landing = landing.or_else(|| course.last().filter(|_| !has_extensions).cloned());
+
+    task.waypoint_names.extend(takeoff);
+    task.waypoint_names.extend(course);
+    task.waypoint_names.extend(landing);
+
+    if let Some(options) = extension(node, "options") {
+        task.options = Some(TaskOptions {
+            no_start: options.attribute("no_start").map(ToString::to_string),
+            task_time: options.attribute("task_time").map(ToString::to_string),
+            wp_dis: parse_attribute(options, "wp_dis", warnings),
+            near_dis: parse_attribute(options, "near_dis", warnings),
+            near_alt: parse_attribute(options, "near_alt", warnings),
+            min_dis: parse_attribute(options, "min_dis", warnings),
+            random_order: parse_attribute(options, "random_order", warnings),
+            max_pts: parse_attribute(options, "max_pts", warnings),
+            before_pts: parse_attribute(options, "before_pts", warnings),
+            after_pts: parse_attribute(options, "after_pts", warnings),
+            bonus: parse_attribute(options, "bonus", warnings),
+        });
+    }
+
+    for zone in extensions(node, "obszone") {
+        let index = parse_attribute(zone, "index", warnings);
+        let style = parse_attribute::<u8>(zone, "style", warnings);
+        let Some((index, style)) = index.zip(style.and_then(ObsZoneStyle::from_u8)) else {
+            warnings.push(warning(zone, "Missing ObsZone index or style"));
+            continue;
+        };
+
+        task.observation_zones.push(ObservationZone {
+            index,
+            style,
+            r1: parse_attribute(zone, "r1", warnings),
+            a1: parse_attribute(zone, "a1", warnings),
+            r2: parse_attribute(zone, "r2", warnings),
+            a2: parse_attribute(zone, "a2", warnings),
+            a12: parse_attribute(zone, "a12", warnings),
+            line: parse_attribute(zone, "line", warnings),
+        });
+    }
+
+    let starts = extensions(node, "start").map(|start| text(start).to_string());
+    task.multiple_starts = starts.collect();
+
+    task
+}
+
+/// Child elements with the given name outside of the extension namespace
+fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(move |child| {
+        let tag = child.tag_name();
+        child.is_element() && tag.name() == name && tag.namespace() != Some(GPX_EXTENSION_NAMESPACE)
+    })
+}
+
+/// Elements with the given name in the extension namespace within the
+/// `<extensions>` of `node`
+fn extensions<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    let extensions = children(node, "extensions").flat_map(|extensions| extensions.children());
+    extensions.filter(move |child| {
+        let tag = child.tag_name();
+        child.is_element() && tag.name() == name && tag.namespace() == Some(GPX_EXTENSION_NAMESPACE)
+    })
+}
+
+fn extension<'a, 'input>(node: Node<'a, 'input>, name: &'a str) -> Option<Node<'a, 'input>> {
+    extensions(node, name).next()
+}
+
+fn text<'a>(node: Node<'a, '_>) -> &'a str {
+    node.text().unwrap_or_default().trim()
+}
+
+fn parse_extension<T: FromStr>(node: Node, name: &str, warnings: &mut Vec<Warning>) -> Option<T> {
+    let extension = extension(node, name)?;
+    parse_value(extension, name, text(extension), warnings)
+}
+
+fn parse_attribute<T: FromStr>(node: Node, name: &str, warnings: &mut Vec<Warning>) -> Option<T> {
+    parse_value(node, name, node.attribute(name)?.trim(), warnings)
+}
+
+/// Parses `value`, or adds a warning and returns `None` if it's invalid
+fn parse_value<T: FromStr>(
+    node: Node,
+    name: &str,
+    value: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<T> {
+    let parsed = value.parse().ok();
+    if parsed.is_none() {
+        let message = format!("Ignored field: Invalid {name}: '{value}'");
+        warnings.push(warning(node, message));
+    }
+    parsed
+}
+
+fn warning(node: Node, message: impl Into<String>) -> Warning {
+    let position = node.document().text_pos_at(node.range().start);
+    ParseIssue::new(message)
+        .with_line(position.row as u64)
+        .into()
+}
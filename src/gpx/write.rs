@@ -0,0 +1,169 @@
+use crate::gpx::{GPX_EXTENSION_NAMESPACE, GPX_NAMESPACE, symbol};
+use crate::writer::format_field;
+use crate::xml::XmlWriter;
+use crate::{Column, CupFile, Elevation, Error, ObservationZone, Task, TaskOptions, Waypoint};
+
+/// Waypoint columns without a GPX equivalent, written to the extensions
+/// with their header as element name
+const EXTENSION_COLUMNS: [Column; 8] = [
+    Column::Code,
+    Column::Country,
+    Column::Style,
+    Column::RwDir,
+    Column::RwLen,
+    Column::RwWidth,
+    Column::Freq,
+    Column::Userdata,
+];
+
+pub fn format(cup_file: &CupFile) -> Result<String, Error> {
+    let mut xml = XmlWriter::new();
+    xml.open(
+        "gpx",
+        &[
+            ("version", "1.1"),
+            ("creator", "seeyou-cup"),
+            ("xmlns", GPX_NAMESPACE),
+            ("xmlns:cup", GPX_EXTENSION_NAMESPACE),
+        ],
+    );
+
+    let columns = cup_file.columns.columns().iter().map(Column::header);
+    xml.open("metadata", &[]);
+    xml.open("extensions", &[]);
+    xml.element("cup:columns", &[], &columns.collect::<Vec<_>>().join(","));
+    xml.close("extensions");
+    xml.close("metadata");
+
+    for waypoint in &cup_file.waypoints {
+        write_point(&mut xml, "wpt", waypoint, false);
+    }
+
+    for task in &cup_file.tasks {
+        write_task(&mut xml, task, &cup_file.waypoints)?;
+    }
+
+    xml.close("gpx");
+    Ok(xml.finish())
+}
+
+fn write_point(xml: &mut XmlWriter, tag: &str, waypoint: &Waypoint, inline: bool) {
+    let latitude = waypoint.latitude.to_string();
+    let longitude = waypoint.longitude.to_string();
+    xml.open(tag, &[("lat", &latitude), ("lon", &longitude)]);
+
+    xml.element("ele", &[], &waypoint.elevation.to_meters().to_string());
+    xml.element("name", &[], &waypoint.name);
+    if !waypoint.description.is_empty() {
+        xml.element("desc", &[], &waypoint.description);
+    }
+    xml.element("sym", &[], symbol(waypoint.style));
+    xml.element("type", &[], waypoint.style.description());
+
+    xml.open("extensions", &[]);
+    // `<ele>` is always in meters
+    if let Elevation::Feet(_) = waypoint.elevation {
+        xml.element("cup:elev", &[], &waypoint.elevation.to_string());
+    }
+    for column in EXTENSION_COLUMNS {
+        let value = format_field(waypoint, column);
+        if !value.is_empty() {
+            xml.element(&format!("cup:{}", column.header()), &[], &value);
+        }
+    }
+    for picture in &waypoint.pictures {
+        xml.element("cup:pic", &[], picture);
+    }
+    if inline {
+        xml.empty("cup:inline", &[]);
+    }
+    xml.close("extensions");
+
+    xml.close(tag);
+}
+
+fn write_task(xml: &mut XmlWriter, task: &Task, waypoints: &[Waypoint]) -> Result<(), Error> {
+    let points = task.resolve(waypoints)?;
+
+    xml.open("rte", &[]);
+    if let Some(description) = &task.description {
+        xml.element("name", &[], description);
+    }
+
+    xml.open("extensions", &[]);
+    let names = &task.waypoint_names;
+    if let Some(takeoff) = names.first() {
+        xml.element("cup:takeoff", &[], takeoff);
+    }
+    if let [_, .., landing] = names.as_slice() {
+        xml.element("cup:landing", &[], landing);
+    }
+    if let Some(options) = &task.options {
+        write_options(xml, options);
+    }
+    for zone in &task.observation_zones {
+        write_observation_zone(xml, zone);
+    }
+    for start in &task.multiple_starts {
+        xml.element("cup:start", &[], start);
+    }
+    xml.close("extensions");
+
+    for point in &points {
+        // `Point=` indices count from the takeoff
+        let inline = task
+            .points
+            .iter()
+            .any(|(index, _)| *index == point.index + 1);
+        write_point(xml, "rtept", point.waypoint, inline);
+    }
+
+    xml.close("rte");
+    Ok(())
+}
+
+fn write_options(xml: &mut XmlWriter, options: &TaskOptions) {
+    let attributes = [
+        ("no_start", options.no_start.clone()),
+        ("task_time", options.task_time.clone()),
+        ("wp_dis", options.wp_dis.map(|v| v.to_string())),
+        (
+            "near_dis",
+            options.near_dis.as_ref().map(ToString::to_string),
+        ),
+        (
+            "near_alt",
+            options.near_alt.as_ref().map(ToString::to_string),
+        ),
+        ("min_dis", options.min_dis.map(|v| v.to_string())),
+        ("random_order", options.random_order.map(|v| v.to_string())),
+        ("max_pts", options.max_pts.map(|v| v.to_string())),
+        ("before_pts", options.before_pts.map(|v| v.to_string())),
+        ("after_pts", options.after_pts.map(|v| v.to_string())),
+        ("bonus", options.bonus.map(|v| v.to_string())),
+    ];
+    write_attributes(xml, "cup:options", &attributes);
+}
+
+fn write_observation_zone(xml: &mut XmlWriter, zone: &ObservationZone) {
+    let attributes = [
+        ("index", Some(zone.index.to_string())),
+        ("style", Some((zone.style as u8).to_string())),
+        ("r1", zone.r1.as_ref().map(ToString::to_string)),
+        ("a1", zone.a1.map(|v| v.to_string())),
+        ("r2", zone.r2.as_ref().map(ToString::to_string)),
+        ("a2", zone.a2.map(|v| v.to_string())),
+        ("a12", zone.a12.map(|v| v.to_string())),
+        ("line", zone.line.map(|v| v.to_string())),
+    ];
+    write_attributes(xml, "cup:obszone", &attributes);
+}
+
+/// Writes an empty element with the attributes that have a value
+fn write_attributes(xml: &mut XmlWriter, tag: &str, attributes: &[(&str, Option<String>)]) {
+    let attributes = attributes
+        .iter()
+        .filter_map(|(key, value)| Some((*key, value.as_deref()?)))
+        .collect::<Vec<_>>();
+    xml.empty(tag, &attributes);
+}
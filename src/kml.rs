@@ -0,0 +1,249 @@
+use crate::xml::{XmlWriter, escape};
+use crate::{Coordinate, CupFile, Error, Task, Waypoint, WaypointStyle, ZoneGeometry};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Namespace of the KML 2.2 schema
+const KML_NAMESPACE: &str = "http://www.opengis.net/kml/2.2";
+/// Base URL of the standard Google Earth icons
+const ICONS: &str = "http://maps.google.com/mapfiles/kml/shapes";
+
+impl CupFile {
+    /// Writes the file as KML 2.2 for Google Earth
+    ///
+    /// Every waypoint becomes a placemark with an icon for its style and a
+    /// balloon with its details, runway information and pictures. The
+    /// pictures are linked from the `pics/` folder next to the KML file,
+    /// see [`CupxFile::to_kmz_writer()`](crate::CupxFile::to_kmz_writer)
+    /// to bundle them. Every task becomes a folder with the course as a
+    /// line string and its observation zones as polygons, or as line
+    /// strings for start and finish lines.
+    ///
+    /// Fails if a task refers to a waypoint that isn't defined.
+    pub fn to_kml_writer<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        writer.write_all(self.to_kml_string()?.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn to_kml_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let file = File::create(path)?;
+        self.to_kml_writer(file)
+    }
+
+    pub fn to_kml_string(&self) -> Result<String, Error> {
+        let mut xml = XmlWriter::new();
+        xml.open("kml", &[("xmlns", KML_NAMESPACE)]);
+        xml.open("Document", &[]);
+
+        let styles = self.waypoints.iter().map(|w| w.style as u8);
+        for style in styles.collect::<BTreeSet<_>>() {
+            write_waypoint_style(&mut xml, WaypointStyle::ALL[style as usize]);
+        }
+        write_line_style(&mut xml, "task", "ff0000ff", None);
+        write_line_style(&mut xml, "zone", "ff00ffff", Some("4000ffff"));
+
+        xml.open("Folder", &[]);
+        xml.element("name", &[], "Waypoints");
+        for waypoint in &self.waypoints {
+            write_waypoint(&mut xml, waypoint);
+        }
+        xml.close("Folder");
+
+        if !self.tasks.is_empty() {
+            xml.open("Folder", &[]);
+            xml.element("name", &[], "Tasks");
+            for (index, task) in self.tasks.iter().enumerate() {
+                write_task(&mut xml, task, index, &self.waypoints)?;
+            }
+            xml.close("Folder");
+        }
+
+        xml.close("Document");
+        xml.close("kml");
+        Ok(xml.finish())
+    }
+}
+
+fn write_waypoint_style(xml: &mut XmlWriter, style: WaypointStyle) {
+    let href = format!("{ICONS}/{}.png", icon(style));
+    xml.open("Style", &[("id", &style_id(style))]);
+    xml.open("IconStyle", &[]);
+    if style.is_landable() {
+        xml.element("color", &[], "ff00ff00");
+    }
+    xml.open("Icon", &[]);
+    xml.element("href", &[], &href);
+    xml.close("Icon");
+    // The shapes are centered on the position
+    let center = [("x", "0.5"), ("y", "0.5")];
+    let units = [("xunits", "fraction"), ("yunits", "fraction")];
+    xml.empty("hotSpot", &[center, units].concat());
+    xml.close("IconStyle");
+    xml.close("Style");
+}
+
+/// Writes a style for lines with `color`, and for polygons filled with
+/// `fill`, both given as `aabbggrr`
+fn write_line_style(xml: &mut XmlWriter, id: &str, color: &str, fill: Option<&str>) {
+    xml.open("Style", &[("id", id)]);
+    xml.open("LineStyle", &[]);
+    xml.element("color", &[], color);
+    xml.element("width", &[], "2");
+    xml.close("LineStyle");
+    if let Some(fill) = fill {
+        xml.open("PolyStyle", &[]);
+        xml.element("color", &[], fill);
+        xml.close("PolyStyle");
+    }
+    xml.close("Style");
+}
+
+fn write_waypoint(xml: &mut XmlWriter, waypoint: &Waypoint) {
+    xml.open("Placemark", &[]);
+    xml.element("name", &[], &waypoint.name);
+    xml.element("description", &[], &balloon(waypoint));
+    xml.element("styleUrl", &[], &format!("#{}", style_id(waypoint.style)));
+    xml.open("Point", &[]);
+    let coordinates = format!(
+        "{:.6},{:.6},{}",
+        waypoint.longitude,
+        waypoint.latitude,
+        waypoint.elevation.to_meters()
+    );
+    xml.element("coordinates", &[], &coordinates);
+    xml.close("Point");
+    xml.close("Placemark");
+}
+
+/// HTML content of the balloon of a waypoint
+fn balloon(waypoint: &Waypoint) -> String {
+    let mut rows = Vec::new();
+    let mut row = |label: &str, value: &str| {
+        if !value.is_empty() {
+            rows.push(format!("<b>{label}:</b> {}", escape(value)));
+        }
+    };
+
+    row("Code", &waypoint.code);
+    row("Country", &waypoint.country);
+    row("Type", waypoint.style.description());
+    row("Elevation", &waypoint.elevation.to_string());
+
+    let direction = waypoint.runway_direction.map(|d| format!("{d:03}°"));
+    let length = waypoint.runway_length.as_ref().map(ToString::to_string);
+    let width = waypoint.runway_width.as_ref().map(|w| format!("× {w}"));
+    let runway = [direction, length, width].into_iter().flatten();
+    row("Runway", &runway.collect::<Vec<_>>().join(" "));
+
+    row("Frequency", &waypoint.frequency);
+    row("Description", &waypoint.description);
+
+    for picture in &waypoint.pictures {
+        rows.push(format!(
+            "<img src=\"pics/{}\" width=\"400\"/>",
+            escape(picture)
+        ));
+    }
+    rows.join("<br/>")
+}
+
+fn write_task(
+    xml: &mut XmlWriter,
+    task: &Task,
+    index: usize,
+    waypoints: &[Waypoint],
+) -> Result<(), Error> {
+    let points = task.resolve(waypoints)?;
+    let name = match &task.description {
+        Some(description) => description.clone(),
+        None => format!("Task {}", index + 1),
+    };
+
+    xml.open("Folder", &[]);
+    xml.element("name", &[], &name);
+
+    xml.open("Placemark", &[]);
+    xml.element("name", &[], &name);
+    xml.element("styleUrl", &[], "#task");
+    let course = points.iter().map(|point| point.waypoint);
+    let course = course.map(|waypoint| (waypoint.latitude, waypoint.longitude));
+    write_line_string(xml, course);
+    xml.close("Placemark");
+
+    let zones = ZoneGeometry::for_task(&points);
+    for (point, zone) in points.iter().zip(zones) {
+        // Zones without a radius have no outline
+        if point.observation_zone.is_none_or(|zone| zone.r1.is_none()) {
+            continue;
+        }
+
+        xml.open("Placemark", &[]);
+        xml.element("name", &[], &point.waypoint.name);
+        xml.element("styleUrl", &[], "#zone");
+        let outline = zone.polygon().into_iter();
+        let outline = outline.map(|c: Coordinate| (c.latitude(), c.longitude()));
+        match zone.is_line() {
+            true => write_line_string(xml, outline),
+            false => write_polygon(xml, outline),
+        }
+        xml.close("Placemark");
+    }
+
+    xml.close("Folder");
+    Ok(())
+}
+
+fn write_line_string(xml: &mut XmlWriter, positions: impl Iterator<Item = (f64, f64)>) {
+    xml.open("LineString", &[]);
+    xml.element("tessellate", &[], "1");
+    xml.element("coordinates", &[], &format_coordinates(positions));
+    xml.close("LineString");
+}
+
+fn write_polygon(xml: &mut XmlWriter, ring: impl Iterator<Item = (f64, f64)>) {
+    xml.open("Polygon", &[]);
+    xml.element("tessellate", &[], "1");
+    xml.open("outerBoundaryIs", &[]);
+    xml.open("LinearRing", &[]);
+    xml.element("coordinates", &[], &format_coordinates(ring));
+    xml.close("LinearRing");
+    xml.close("outerBoundaryIs");
+    xml.close("Polygon");
+}
+
+fn format_coordinates(positions: impl Iterator<Item = (f64, f64)>) -> String {
+    let positions = positions.map(|(lat, lon)| format!("{lon:.6},{lat:.6}"));
+    positions.collect::<Vec<_>>().join(" ")
+}
+
+fn style_id(style: WaypointStyle) -> String {
+    format!("style-{}", style as u8)
+}
+
+/// Name of the Google Earth icon for a waypoint style
+fn icon(style: WaypointStyle) -> &'static str {
+    match style {
+        WaypointStyle::GrassAirfield
+        | WaypointStyle::GlidingAirfield
+        | WaypointStyle::SolidAirfield => "airports",
+        WaypointStyle::Outlanding | WaypointStyle::PgLandingZone => "flag",
+        WaypointStyle::MountainPass | WaypointStyle::MountainTop | WaypointStyle::PgTakeOff => {
+            "triangle"
+        }
+        WaypointStyle::TransmitterMast
+        | WaypointStyle::Vor
+        | WaypointStyle::Ndb
+        | WaypointStyle::CoolingTower
+        | WaypointStyle::Dam
+        | WaypointStyle::Tunnel
+        | WaypointStyle::Bridge
+        | WaypointStyle::PowerPlant
+        | WaypointStyle::Castle => "square",
+        WaypointStyle::Intersection | WaypointStyle::ControlPoint => "target",
+        WaypointStyle::Unknown | WaypointStyle::Waypoint | WaypointStyle::Marker => {
+            "placemark_circle"
+        }
+    }
+}
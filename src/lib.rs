@@ -1,12 +1,49 @@
 #![doc = include_str!("../README.md")]
 
+mod codes;
+#[cfg(feature = "cupx")]
+mod cupx;
+mod diff;
+mod edit;
 mod error;
+mod extract;
+mod filter;
+pub mod geodesy;
+#[cfg(feature = "geojson")]
+mod geojson;
+#[cfg(feature = "gpx")]
+mod gpx;
+mod kml;
+mod lookup;
+mod merge;
 mod parser;
+mod reach;
+mod search;
+mod spatial;
 pub mod spec;
+mod task;
+mod transliterate;
 mod types;
 mod writer;
+mod xml;
 
-pub use error::{Error, Warning};
+pub use codes::*;
+#[cfg(feature = "cupx")]
+pub use cupx::*;
+pub use diff::*;
+pub use edit::*;
+pub use error::{Error, Warning, WarningKind};
+pub use filter::*;
+#[cfg(feature = "geojson")]
+pub use geojson::*;
+#[cfg(feature = "gpx")]
+pub use gpx::*;
+pub use lookup::*;
+pub use merge::*;
+pub use reach::*;
+pub use search::*;
+pub use spatial::*;
+pub use task::*;
 pub use types::*;
 
 use std::fs::File;
@@ -23,6 +60,19 @@ pub enum Encoding {
     Windows1252,
 }
 
+/// Policy for characters that can't be represented in the target encoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EncodingFallback {
+    /// Fail with [`Error::Encoding`]
+    #[default]
+    Error,
+    /// Replace unsupported characters with `?`
+    Replace,
+    /// Replace unsupported characters with the closest Latin letters
+    /// (e.g. `ł` → `l`), or `?` if there is no such replacement
+    Transliterate,
+}
+
 /// SeeYou CUP file representation
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct CupFile {
@@ -30,6 +80,9 @@ pub struct CupFile {
     pub waypoints: Vec<Waypoint>,
     /// Tasks defined in the file
     pub tasks: Vec<Task>,
+    /// Order of the waypoint columns, used for the header line, the
+    /// waypoints and the inline task points when writing the file
+    pub columns: ColumnLayout,
 }
 
 impl CupFile {
@@ -72,7 +125,21 @@ impl CupFile {
         writer: W,
         encoding: Encoding,
     ) -> Result<(), Error> {
-        writer::write(self, writer, encoding)
+        writer::write(self, writer, encoding, EncodingFallback::Error)?;
+        Ok(())
+    }
+
+    /// Writes the file with `encoding`, handling unsupported characters according to `fallback`
+    ///
+    /// Returns a warning of kind [`WarningKind::EncodingFallback`] for every
+    /// waypoint and task that had to be changed.
+    pub fn to_writer_with_fallback<W: Write>(
+        &self,
+        writer: W,
+        encoding: Encoding,
+        fallback: EncodingFallback,
+    ) -> Result<Vec<Warning>, Error> {
+        writer::write(self, writer, encoding, fallback)
     }
 
     pub fn to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
@@ -88,6 +155,16 @@ impl CupFile {
         self.to_writer_with_encoding(file, encoding)
     }
 
+    pub fn to_path_with_fallback<P: AsRef<Path>>(
+        &self,
+        path: P,
+        encoding: Encoding,
+        fallback: EncodingFallback,
+    ) -> Result<Vec<Warning>, Error> {
+        let file = File::create(path)?;
+        self.to_writer_with_fallback(file, encoding, fallback)
+    }
+
     pub fn to_string(&self) -> Result<String, Error> {
         let mut buf = Vec::new();
         self.to_writer(&mut buf)?;
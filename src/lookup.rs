@@ -0,0 +1,119 @@
+use crate::{CupFile, Waypoint};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+/// Hash index over the waypoints of a file for lookups by name and code
+///
+/// Created by [`CupFile::index()`] or [`WaypointIndex::new()`]. The index
+/// borrows the waypoints, so it has to be rebuilt after they changed. If
+/// several waypoints share a name or code, lookups return the first one.
+#[derive(Debug, Clone)]
+pub struct WaypointIndex<'a> {
+    waypoints: &'a [Waypoint],
+    names: HashMap<&'a str, usize>,
+    folded_names: HashMap<String, usize>,
+    codes: HashMap<&'a str, usize>,
+    folded_codes: HashMap<String, usize>,
+    duplicates: Vec<DuplicateNames>,
+}
+
+/// Waypoints sharing the same name, found by [`WaypointIndex::new()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateNames {
+    /// The shared name
+    pub name: String,
+    /// Indices of all waypoints with this name, in file order
+    pub indices: Vec<usize>,
+}
+
+impl CupFile {
+    /// Builds a [`WaypointIndex`] over the waypoints of the file
+    pub fn index(&self) -> WaypointIndex<'_> {
+        WaypointIndex::new(&self.waypoints)
+    }
+}
+
+impl<'a> WaypointIndex<'a> {
+    /// Builds an index over `waypoints`
+    pub fn new(waypoints: &'a [Waypoint]) -> Self {
+        let mut names = HashMap::new();
+        let mut folded_names = HashMap::new();
+        let mut codes = HashMap::new();
+        let mut folded_codes = HashMap::new();
+        let mut duplicates: Vec<DuplicateNames> = Vec::new();
+        let mut groups = HashMap::new();
+
+        for (index, waypoint) in waypoints.iter().enumerate() {
+            match names.entry(waypoint.name.as_str()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(index);
+                }
+                Entry::Occupied(entry) => {
+                    let first = *entry.get();
+                    let group = *groups.entry(first).or_insert_with(|| {
+                        duplicates.push(DuplicateNames {
+                            name: waypoint.name.clone(),
+                            indices: vec![first],
+                        });
+                        duplicates.len() - 1
+                    });
+                    duplicates[group].indices.push(index);
+                }
+            }
+            folded_names
+                .entry(waypoint.name.to_lowercase())
+                .or_insert(index);
+
+            if !waypoint.code.is_empty() {
+                codes.entry(waypoint.code.as_str()).or_insert(index);
+                folded_codes
+                    .entry(waypoint.code.to_lowercase())
+                    .or_insert(index);
+            }
+        }
+
+        Self {
+            waypoints,
+            names,
+            folded_names,
+            codes,
+            folded_codes,
+            duplicates,
+        }
+    }
+
+    /// Position of the waypoint with exactly this name in the indexed slice
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    /// Waypoint with exactly this name
+    pub fn by_name(&self, name: &str) -> Option<&'a Waypoint> {
+        self.get(self.position(name))
+    }
+
+    /// Waypoint with this name, ignoring case
+    pub fn by_name_ignore_case(&self, name: &str) -> Option<&'a Waypoint> {
+        self.get(self.folded_names.get(&name.to_lowercase()).copied())
+    }
+
+    /// Waypoint with exactly this code, empty codes are not indexed
+    pub fn by_code(&self, code: &str) -> Option<&'a Waypoint> {
+        self.get(self.codes.get(code).copied())
+    }
+
+    /// Waypoint with this code, ignoring case
+    pub fn by_code_ignore_case(&self, code: &str) -> Option<&'a Waypoint> {
+        self.get(self.folded_codes.get(&code.to_lowercase()).copied())
+    }
+
+    /// Groups of waypoints with identical names, which the CUP format
+    /// doesn't allow, in the order of their first occurrence
+    pub fn duplicates(&self) -> &[DuplicateNames] {
+        &self.duplicates
+    }
+
+    fn get(&self, index: Option<usize>) -> Option<&'a Waypoint> {
+        index.map(|index| &self.waypoints[index])
+    }
+}
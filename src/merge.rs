@@ -0,0 +1,250 @@
+use crate::geodesy::EarthModel;
+use crate::{ColumnLayout, CupFile, Distance, Waypoint};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+const MODEL: EarthModel = EarthModel::Wgs84;
+
+/// Criterion for treating two waypoints as the same point
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchBy {
+    /// Identical names
+    Name,
+    /// Identical, non-empty codes
+    Code,
+    /// Positions within the given distance
+    Distance(Distance),
+}
+
+/// Which waypoint to keep if two waypoints match
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keep the waypoint of the file that is merged into
+    #[default]
+    KeepLeft,
+    /// Keep the waypoint of the file that is merged
+    KeepRight,
+    /// Keep the waypoint with more filled-in fields, or the left one if equal
+    PreferRicher,
+}
+
+/// Side of a merge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeSide {
+    Left,
+    Right,
+}
+
+/// Configuration for [`CupFile::merge()`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeOptions {
+    /// Waypoints match if any of the criteria applies
+    pub matching: Vec<MatchBy>,
+    /// Which waypoint to keep if two waypoints match
+    pub resolution: Resolution,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        Self {
+            matching: vec![MatchBy::Name],
+            resolution: Resolution::default(),
+        }
+    }
+}
+
+/// Outcome of [`CupFile::merge()`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    /// Names of the waypoints added from the right file, after renaming
+    pub added: Vec<String>,
+    /// Waypoints of the right file that matched a waypoint of the left file
+    pub matched: Vec<MergeMatch>,
+    /// Waypoints that were renamed because their name was already taken
+    pub renamed: Vec<Rename>,
+    /// Number of tasks added from the right file
+    pub tasks_added: usize,
+}
+
+/// Pair of matching waypoints in a [`MergeReport`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeMatch {
+    /// Name of the waypoint in the left file
+    pub left: String,
+    /// Name of the waypoint in the right file
+    pub right: String,
+    /// Which of the two waypoints was kept
+    pub kept: MergeSide,
+}
+
+/// Renamed waypoint in a [`MergeReport`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rename {
+    /// Original name
+    pub from: String,
+    /// New name
+    pub to: String,
+}
+
+impl Display for MergeReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for name in &self.added {
+            writeln!(f, "Added '{name}'")?;
+        }
+        for m in &self.matched {
+            let kept = match m.kept {
+                MergeSide::Left => "left",
+                MergeSide::Right => "right",
+            };
+            writeln!(f, "Matched '{}' with '{}', kept {kept}", m.left, m.right)?;
+        }
+        for rename in &self.renamed {
+            writeln!(f, "Renamed '{}' to '{}'", rename.from, rename.to)?;
+        }
+        writeln!(f, "Tasks added: {}", self.tasks_added)
+    }
+}
+
+impl MatchBy {
+    fn matches(&self, left: &Waypoint, right: &Waypoint) -> bool {
+        match self {
+            MatchBy::Name => left.name == right.name,
+            MatchBy::Code => !left.code.is_empty() && left.code == right.code,
+            MatchBy::Distance(distance) => {
+                let (meters, _) = MODEL.inverse(left.position(), right.position());
+                meters <= distance.to_meters()
+            }
+        }
+    }
+}
+
+impl CupFile {
+    /// Merges the waypoints and tasks of `other` into this file
+    ///
+    /// Waypoints of `other` that match an existing waypoint are resolved
+    /// according to [`MergeOptions::resolution`], all others are appended.
+    /// Appended waypoints whose name is already taken get a numeric suffix,
+    /// e.g. `Name (2)`. The tasks of `other` are appended, and the task
+    /// references of both files are updated to the names of the kept
+    /// waypoints.
+    pub fn merge(&mut self, other: CupFile, options: &MergeOptions) -> MergeReport {
+        let mut report = MergeReport::default();
+        let mut names = self
+            .waypoints
+            .iter()
+            .map(|waypoint| waypoint.name.clone())
+            .collect::<HashSet<_>>();
+
+        // New names for task references of the left and right file
+        let mut left_renames = HashMap::new();
+        let mut right_renames = HashMap::new();
+
+        let left_count = self.waypoints.len();
+        for right in other.waypoints {
+            let matched = self.waypoints[..left_count]
+                .iter()
+                .position(|left| options.matching.iter().any(|by| by.matches(left, &right)));
+
+            let Some(index) = matched else {
+                let mut name = right.name.clone();
+                if names.contains(&name) {
+                    name = unique_name(&name, &names);
+                    report.renamed.push(Rename {
+                        from: right.name.clone(),
+                        to: name.clone(),
+                    });
+                }
+                if name != right.name {
+                    right_renames
+                        .entry(right.name.clone())
+                        .or_insert(name.clone());
+                }
+
+                names.insert(name.clone());
+                report.added.push(name.clone());
+                self.waypoints.push(Waypoint { name, ..right });
+                continue;
+            };
+
+            let left = &mut self.waypoints[index];
+            let kept = match options.resolution {
+                Resolution::KeepLeft => MergeSide::Left,
+                Resolution::KeepRight => MergeSide::Right,
+                Resolution::PreferRicher if richness(&right) > richness(left) => MergeSide::Right,
+                Resolution::PreferRicher => MergeSide::Left,
+            };
+
+            report.matched.push(MergeMatch {
+                left: left.name.clone(),
+                right: right.name.clone(),
+                kept,
+            });
+
+            let kept_name = match kept {
+                MergeSide::Left => left.name.clone(),
+                MergeSide::Right => {
+                    // A right name that is taken by another waypoint keeps the left name
+                    let name = match right.name != left.name && names.contains(&right.name) {
+                        true => left.name.clone(),
+                        false => right.name.clone(),
+                    };
+                    if name != left.name {
+                        names.remove(&left.name);
+                        names.insert(name.clone());
+                        left_renames.insert(left.name.clone(), name.clone());
+                    }
+                    *left = Waypoint {
+                        name: name.clone(),
+                        ..right.clone()
+                    };
+                    name
+                }
+            };
+            if kept_name != right.name {
+                right_renames.entry(right.name).or_insert(kept_name);
+            }
+        }
+
+        for task in &mut self.tasks {
+            task.rename_references(|name| left_renames.get(name).cloned());
+        }
+
+        report.tasks_added = other.tasks.len();
+        for mut task in other.tasks {
+            task.rename_references(|name| right_renames.get(name).cloned());
+            self.tasks.push(task);
+        }
+
+        let columns = self.columns.columns().iter().chain(other.columns.columns());
+        self.columns = ColumnLayout::new(columns.copied());
+
+        report
+    }
+}
+
+/// Number of optional fields with a value
+fn richness(waypoint: &Waypoint) -> usize {
+    [
+        !waypoint.code.is_empty(),
+        !waypoint.country.is_empty(),
+        waypoint.elevation.to_meters() != 0.0,
+        waypoint.runway_direction.is_some(),
+        waypoint.runway_length.is_some(),
+        waypoint.runway_width.is_some(),
+        !waypoint.frequency.is_empty(),
+        !waypoint.description.is_empty(),
+        !waypoint.userdata.is_empty(),
+        !waypoint.pictures.is_empty(),
+    ]
+    .into_iter()
+    .filter(|filled| *filled)
+    .count()
+}
+
+/// Appends the lowest free numeric suffix to `name`
+pub(crate) fn unique_name(name: &str, names: &HashSet<String>) -> String {
+    (2..)
+        .map(|n| format!("{name} ({n})"))
+        .find(|candidate| !names.contains(candidate))
+        .unwrap_or_else(|| name.to_string())
+}
@@ -1,3 +1,4 @@
+use crate::Column;
 use csv::StringRecord;
 
 pub struct ColumnMap {
@@ -37,22 +38,22 @@ impl TryFrom<&StringRecord> for ColumnMap {
         let mut pics = None;
 
         for (idx, header) in record.iter().enumerate() {
-            match header.to_lowercase().as_str() {
-                "name" => name = Some(idx),
-                "code" => code = Some(idx),
-                "country" => country = Some(idx),
-                "lat" => lat = Some(idx),
-                "lon" => lon = Some(idx),
-                "elev" => elev = Some(idx),
-                "style" => style = Some(idx),
-                "rwdir" => rwdir = Some(idx),
-                "rwlen" => rwlen = Some(idx),
-                "rwwidth" => rwwidth = Some(idx),
-                "freq" => freq = Some(idx),
-                "desc" => desc = Some(idx),
-                "userdata" => userdata = Some(idx),
-                "pics" => pics = Some(idx),
-                _ => {}
+            match Column::from_header(header) {
+                Some(Column::Name) => name = Some(idx),
+                Some(Column::Code) => code = Some(idx),
+                Some(Column::Country) => country = Some(idx),
+                Some(Column::Lat) => lat = Some(idx),
+                Some(Column::Lon) => lon = Some(idx),
+                Some(Column::Elev) => elev = Some(idx),
+                Some(Column::Style) => style = Some(idx),
+                Some(Column::RwDir) => rwdir = Some(idx),
+                Some(Column::RwLen) => rwlen = Some(idx),
+                Some(Column::RwWidth) => rwwidth = Some(idx),
+                Some(Column::Freq) => freq = Some(idx),
+                Some(Column::Desc) => desc = Some(idx),
+                Some(Column::Userdata) => userdata = Some(idx),
+                Some(Column::Pics) => pics = Some(idx),
+                None => {}
             }
         }
 
@@ -1,18 +1,19 @@
-mod basics;
+pub mod basics;
 mod column_map;
 mod task;
 mod waypoint;
 
-use crate::CupFile;
-use crate::Encoding;
 use crate::error::{Error, ParseIssue, Warning};
 use crate::parser::column_map::ColumnMap;
 use crate::parser::task::parse_tasks;
 use crate::parser::waypoint::parse_waypoints;
+use crate::{Column, ColumnLayout, CupFile, Encoding};
 use encoding_rs::{Encoding as EncodingImpl, UTF_8, WINDOWS_1252};
 use std::borrow::Cow;
 use std::io::Read;
 
+pub(crate) use crate::parser::waypoint::parse_waypoint_style;
+
 pub const TASK_SEPARATOR: &str = "-----Related Tasks-----";
 
 pub fn parse<R: Read>(
@@ -67,10 +68,17 @@ fn parse_content(content: &str) -> Result<(CupFile, Vec<Warning>), Error> {
     let headers = csv_reader.headers()?;
     let column_map = ColumnMap::try_from(headers)
         .map_err(|error| ParseIssue::new(error).with_record(headers))?;
+    let columns = ColumnLayout::new(headers.iter().filter_map(Column::from_header));
 
     let mut csv_iter = csv_reader.records();
     let waypoints = parse_waypoints(&mut csv_iter, &column_map, &mut warnings)?;
-    let tasks = parse_tasks(&mut csv_iter, &column_map, &mut warnings)?;
+    let tasks = parse_tasks(&mut csv_iter, content, &column_map, &mut warnings)?;
+
+    let cup_file = CupFile {
+        waypoints,
+        tasks,
+        columns,
+    };
 
-    Ok((CupFile { waypoints, tasks }, warnings))
+    Ok((cup_file, warnings))
 }
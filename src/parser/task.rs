@@ -6,6 +6,7 @@ use csv::StringRecord;
 
 pub fn parse_tasks(
     csv_iter: &mut csv::StringRecordsIter<&[u8]>,
+    content: &str,
     column_map: &ColumnMap,
     warnings: &mut Vec<Warning>,
 ) -> Result<Vec<Task>, Error> {
@@ -24,7 +25,7 @@ pub fn parse_tasks(
             continue;
         }
 
-        let mut task = parse_task_line(&record)?;
+        let mut task = parse_task_line(&record, content)?;
 
         // Look ahead for Options, ObsZone, Point, and STARTS lines
         while let Some(result) = csv_iter.peek() {
@@ -60,7 +61,7 @@ pub fn parse_tasks(
     Ok(tasks)
 }
 
-fn parse_task_line(record: &StringRecord) -> Result<Task, Error> {
+fn parse_task_line(record: &StringRecord, content: &str) -> Result<Task, Error> {
     if record.is_empty() {
         return Err(ParseIssue::new("Empty task line").into());
     }
@@ -71,7 +72,17 @@ fn parse_task_line(record: &StringRecord) -> Result<Task, Error> {
         Some(record.get(0).unwrap().to_string())
     };
 
-    let waypoint_names = record.iter().skip(1).map(|s| s.to_string()).collect();
+    let mut waypoint_names = record
+        .iter()
+        .skip(1)
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+
+    // The task lines in the specification end with a comma, which isn't an
+    // empty landing. Those are written as `""`, like SeeYou does.
+    if has_trailing_comma(record, content) && waypoint_names.last().is_some_and(String::is_empty) {
+        waypoint_names.pop();
+    }
 
     Ok(Task {
         description,
@@ -83,6 +94,14 @@ fn parse_task_line(record: &StringRecord) -> Result<Task, Error> {
     })
 }
 
+/// Whether the line of the record in `content` ends with an unquoted comma
+fn has_trailing_comma(record: &StringRecord, content: &str) -> bool {
+    let start = record.position().map_or(0, |p| p.byte() as usize);
+    let line = content.get(start..).unwrap_or_default();
+    let line = line.lines().next().unwrap_or_default();
+    line.trim_end().ends_with(',')
+}
+
 fn parse_options_line(record: &StringRecord) -> Result<TaskOptions, Error> {
     // Options,NoStart=12:34:56,TaskTime=01:45:12,WpDis=False,NearDis=0.7km,NearAlt=300.0m
     let mut options = TaskOptions {
@@ -169,12 +188,17 @@ fn parse_obszone_line(record: &StringRecord) -> Result<ObservationZone, Error> {
 
 fn parse_starts_line(record: &StringRecord) -> Result<Vec<String>, Error> {
     // STARTS=Celovec,Hodos,Ratitovec,Jamnik
+    // The first entry keeps its quotes, since the field doesn't start with one
     Ok(record
         .iter()
         .enumerate()
         .map(|(i, start)| {
             if i == 0 {
-                start.strip_prefix("STARTS=").unwrap_or(start)
+                let start = start.strip_prefix("STARTS=").unwrap_or(start);
+                start
+                    .strip_prefix('"')
+                    .and_then(|s| s.strip_suffix('"'))
+                    .unwrap_or(start)
             } else {
                 start
             }
@@ -128,7 +128,7 @@ pub fn parse_waypoint(
     })
 }
 
-fn parse_waypoint_style(s: &str) -> Option<WaypointStyle> {
+pub(crate) fn parse_waypoint_style(s: &str) -> Option<WaypointStyle> {
     Some(match s {
         "0" => WaypointStyle::Unknown,
         "1" => WaypointStyle::Waypoint,
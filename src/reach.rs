@@ -0,0 +1,135 @@
+use crate::geodesy::EarthModel;
+use crate::spatial::{SpatialIndex, StyleFilter};
+use crate::{Coordinate, CupFile, Distance, Elevation, Waypoint};
+
+const MODEL: EarthModel = EarthModel::Wgs84;
+
+/// Current state of the glider for [`SpatialIndex::reachable_landables()`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlideParameters {
+    /// Current position
+    pub position: Coordinate,
+    /// Current altitude above sea level
+    pub altitude: Elevation,
+    /// Still-air glide ratio, e.g. `40.0` for 1:40
+    pub glide_ratio: f64,
+    /// Airspeed in km/h at which the glide ratio is achieved, only used with `wind`
+    pub airspeed: f64,
+    /// Minimum height above a landable on arrival
+    pub safety_altitude: Elevation,
+    /// Wind, if any
+    pub wind: Option<Wind>,
+}
+
+/// Wind vector
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wind {
+    /// Direction the wind is blowing from in degrees
+    pub direction: f64,
+    /// Wind speed in km/h
+    pub speed: f64,
+}
+
+/// Landable waypoint found by [`SpatialIndex::reachable_landables()`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReachableLandable<'a> {
+    /// Index of the waypoint in the indexed slice
+    pub index: usize,
+    /// The waypoint itself
+    pub waypoint: &'a Waypoint,
+    /// Distance from the current position
+    pub distance: Distance,
+    /// Bearing in degrees from the current position
+    pub bearing: f64,
+    /// Height above the waypoint elevation on arrival, including the safety altitude
+    pub arrival_height: Elevation,
+}
+
+impl GlideParameters {
+    /// Glide ratio over ground on the given course, or `None` if the wind
+    /// doesn't allow any progress
+    fn glide_ratio_on(&self, bearing: f64) -> Option<f64> {
+        let Some(wind) = self.wind.filter(|wind| wind.speed != 0.0) else {
+            return Some(self.glide_ratio);
+        };
+
+        // Angle between the course and the direction the wind is blowing to
+        let angle = (bearing - (wind.direction + 180.0)).to_radians();
+        let tailwind = wind.speed * angle.cos();
+        let crosswind = wind.speed * angle.sin();
+
+        let along = self.airspeed.powi(2) - crosswind.powi(2);
+        let ground_speed = along.max(0.0).sqrt() + tailwind;
+        if along < 0.0 || ground_speed <= 0.0 || self.airspeed <= 0.0 {
+            return None;
+        }
+        Some(self.glide_ratio * ground_speed / self.airspeed)
+    }
+
+    /// Best possible glide ratio over ground in any direction
+    fn max_glide_ratio(&self) -> f64 {
+        match self.wind {
+            Some(wind) if self.airspeed > 0.0 => {
+                self.glide_ratio * (self.airspeed + wind.speed.abs()) / self.airspeed
+            }
+            _ => self.glide_ratio,
+        }
+    }
+}
+
+impl CupFile {
+    /// Landable waypoints reachable in a straight glide, highest arrival first
+    ///
+    /// See [`SpatialIndex::reachable_landables()`].
+    pub fn reachable_landables(&self, glide: &GlideParameters) -> Vec<ReachableLandable<'_>> {
+        self.spatial_index().reachable_landables(glide)
+    }
+}
+
+impl<'a> SpatialIndex<'a> {
+    /// Landable waypoints reachable in a straight glide, highest arrival first
+    ///
+    /// A waypoint is reachable if the glider arrives at least
+    /// [`GlideParameters::safety_altitude`] above its elevation. Only
+    /// waypoints for which [`WaypointStyle::is_landable()`](crate::WaypointStyle::is_landable)
+    /// is true are considered. Terrain between the position and the
+    /// waypoint is not taken into account.
+    pub fn reachable_landables(&self, glide: &GlideParameters) -> Vec<ReachableLandable<'a>> {
+        let altitude = glide.altitude.to_meters();
+        let safety = glide.safety_altitude.to_meters();
+
+        let height = altitude - safety - self.lowest_elevation();
+        if height < 0.0 || glide.glide_ratio <= 0.0 {
+            return Vec::new();
+        }
+
+        let radius = Distance::Meters(height * glide.max_glide_ratio());
+        let candidates = self.within_radius(&glide.position, &radius, &StyleFilter::Landables);
+
+        let mut reachable = candidates
+            .into_iter()
+            .filter_map(|neighbor| {
+                let waypoint = neighbor.waypoint;
+                let (meters, bearing) =
+                    MODEL.inverse(glide.position.position(), waypoint.position());
+                let ratio = glide.glide_ratio_on(bearing)?;
+
+                let arrival = altitude - meters / ratio - waypoint.elevation.to_meters();
+                (arrival >= safety).then_some(ReachableLandable {
+                    index: neighbor.index,
+                    waypoint,
+                    distance: neighbor.distance,
+                    bearing,
+                    arrival_height: Elevation::Meters(arrival),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        reachable.sort_by(|a, b| {
+            let a = a.arrival_height.to_meters();
+            let b = b.arrival_height.to_meters();
+            b.total_cmp(&a)
+        });
+        reachable
+    }
+}
@@ -0,0 +1,191 @@
+use crate::geodesy::EarthModel;
+use crate::transliterate::transliterate;
+use crate::{Coordinate, CupFile, Distance, Waypoint};
+
+const MODEL: EarthModel = EarthModel::Wgs84;
+
+/// Configuration for [`CupFile::search()`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchOptions {
+    /// Maximum number of results
+    pub limit: usize,
+    /// Minimum score between 0 and 1 of a result
+    pub min_score: f64,
+    /// Position to prefer nearby waypoints to, if any
+    pub reference: Option<Coordinate>,
+    /// Distance from [`SearchOptions::reference`] at which a waypoint loses
+    /// a quarter of its score
+    pub distance_scale: Distance,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: 10,
+            min_score: 0.5,
+            reference: None,
+            distance_scale: Distance::Kilometers(50.0),
+        }
+    }
+}
+
+/// Waypoint found by [`CupFile::search()`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult<'a> {
+    /// Index of the waypoint in [`CupFile::waypoints`]
+    pub index: usize,
+    /// The waypoint itself
+    pub waypoint: &'a Waypoint,
+    /// Similarity between 0 and 1, including the distance weighting
+    pub score: f64,
+    /// Distance from [`SearchOptions::reference`], if given
+    pub distance: Option<Distance>,
+}
+
+impl CupFile {
+    /// Waypoints whose name or code resembles `query`, best match first
+    ///
+    /// Every word of the query is compared with the words of the name,
+    /// accepting prefixes (`aachen brnd` finds `Aachen Brand`), omitted
+    /// letters and small typos. Numeric prefixes like `002` or `000_` are
+    /// ignored, and letters with diacritics match their ASCII spelling
+    /// (`wurselen`, `würselen` and `wuerselen` all find `Wuerselen`).
+    ///
+    /// With a [`SearchOptions::reference`] position the score is scaled
+    /// down with the distance, to at most half for very distant waypoints.
+    pub fn search(&self, query: &str, options: &SearchOptions) -> Vec<SearchResult<'_>> {
+        let query_words = words(query);
+        if query_words.is_empty() {
+            return Vec::new();
+        }
+        let query_code = query_words.concat();
+
+        let mut results = self
+            .waypoints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, waypoint)| {
+                let name_words = words(&waypoint.name);
+                let code = fold(&waypoint.code);
+                let score =
+                    name_score(&query_words, &name_words).max(code_score(&query_code, &code));
+
+                let distance = options.reference.map(|reference| {
+                    let (meters, _) = MODEL.inverse(reference.position(), waypoint.position());
+                    Distance::Meters(meters)
+                });
+                let score = match &distance {
+                    Some(distance) => {
+                        let scale = options.distance_scale.to_meters();
+                        let weight = scale / (scale + distance.to_meters());
+                        score * (1.0 + weight) / 2.0
+                    }
+                    None => score,
+                };
+
+                (score >= options.min_score && score > 0.0).then_some(SearchResult {
+                    index,
+                    waypoint,
+                    score,
+                    distance,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(options.limit);
+        results
+    }
+}
+
+/// Lower-case ASCII spelling of `text`
+fn fold(text: &str) -> String {
+    text.chars()
+        .map(|c| transliterate(c).map_or_else(|| c.to_string(), str::to_string))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Words of `text` without a leading number
+fn words(text: &str) -> Vec<String> {
+    let folded = fold(text);
+    let mut words = folded
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    if words.len() > 1 && words[0].chars().all(|c| c.is_ascii_digit()) {
+        words.remove(0);
+    }
+    words
+}
+
+/// Average similarity of every query word with its best matching name
+/// word, or zero if any query word has no match
+fn name_score(query: &[String], name: &[String]) -> f64 {
+    let mut total = 0.0;
+    for query_word in query {
+        let best = name
+            .iter()
+            .map(|name_word| word_score(query_word, name_word))
+            .fold(0.0, f64::max);
+        if best == 0.0 {
+            return 0.0;
+        }
+        total += best;
+    }
+    total / query.len() as f64
+}
+
+fn word_score(query: &str, word: &str) -> f64 {
+    if query == word {
+        return 1.0;
+    }
+    if word.starts_with(query) {
+        return 0.9;
+    }
+
+    // Abbreviations like `brnd` for `brand`, or `wurselen` for `wuerselen`
+    let query_len = query.chars().count() as f64;
+    let word_len = word.chars().count() as f64;
+    if is_subsequence(query, word) && word.chars().next() == query.chars().next() {
+        return 0.6 + 0.3 * query_len / word_len;
+    }
+
+    // Typos
+    let similarity = 1.0 - levenshtein(query, word) as f64 / query_len.max(word_len);
+    match similarity >= 0.6 {
+        true => similarity * 0.8,
+        false => 0.0,
+    }
+}
+
+fn code_score(query: &str, code: &str) -> f64 {
+    match code {
+        "" => 0.0,
+        code if code == query => 1.0,
+        code if code.starts_with(query) && query.len() >= 2 => 0.8,
+        _ => 0.0,
+    }
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|c| haystack.any(|h| h == c))
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
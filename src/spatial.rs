@@ -0,0 +1,235 @@
+use crate::geodesy::EarthModel;
+use crate::{Coordinate, CupFile, Distance, Waypoint, WaypointStyle};
+use std::collections::HashMap;
+
+const MODEL: EarthModel = EarthModel::Wgs84;
+
+/// Size of the grid cells in degrees
+const CELL_SIZE: f64 = 0.25;
+/// Lower bound of the length of one degree of latitude in meters
+const METERS_PER_DEGREE: f64 = 110_000.0;
+/// Half of the earth's circumference in meters, the largest possible distance
+const MAX_DISTANCE: f64 = 20_040_000.0;
+
+/// Grid index over the waypoints of a file for fast proximity queries
+///
+/// Created by [`CupFile::spatial_index()`] or [`SpatialIndex::new()`]. The
+/// index borrows the waypoints, so it has to be rebuilt after they changed.
+#[derive(Debug, Clone)]
+pub struct SpatialIndex<'a> {
+    waypoints: &'a [Waypoint],
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    /// Lowest elevation of all waypoints in meters
+    lowest_elevation: f64,
+}
+
+/// Waypoint found by a [`SpatialIndex`] query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Neighbor<'a> {
+    /// Index of the waypoint in the indexed slice
+    pub index: usize,
+    /// The waypoint itself
+    pub waypoint: &'a Waypoint,
+    /// Distance from the query position
+    pub distance: Distance,
+}
+
+/// Waypoint styles included in a [`SpatialIndex`] query
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StyleFilter {
+    /// All waypoints
+    #[default]
+    All,
+    /// Waypoints for which [`WaypointStyle::is_landable()`] is true
+    Landables,
+    /// Waypoints with one of the given styles
+    Only(Vec<WaypointStyle>),
+}
+
+impl StyleFilter {
+    /// Whether waypoints with `style` are included
+    pub fn matches(&self, style: WaypointStyle) -> bool {
+        match self {
+            StyleFilter::All => true,
+            StyleFilter::Landables => style.is_landable(),
+            StyleFilter::Only(styles) => styles.contains(&style),
+        }
+    }
+}
+
+impl CupFile {
+    /// Builds a [`SpatialIndex`] over the waypoints of the file
+    pub fn spatial_index(&self) -> SpatialIndex<'_> {
+        SpatialIndex::new(&self.waypoints)
+    }
+}
+
+impl<'a> SpatialIndex<'a> {
+    /// Builds an index over `waypoints`
+    pub fn new(waypoints: &'a [Waypoint]) -> Self {
+        let mut cells: HashMap<_, Vec<_>> = HashMap::new();
+        for (index, waypoint) in waypoints.iter().enumerate() {
+            let cell = cell_of(waypoint.latitude, waypoint.longitude);
+            cells.entry(cell).or_default().push(index);
+        }
+
+        let lowest_elevation = waypoints
+            .iter()
+            .map(|waypoint| waypoint.elevation.to_meters())
+            .fold(f64::INFINITY, f64::min);
+
+        Self {
+            waypoints,
+            cells,
+            lowest_elevation,
+        }
+    }
+
+    pub(crate) fn lowest_elevation(&self) -> f64 {
+        self.lowest_elevation
+    }
+
+    /// Up to `k` waypoints closest to `position`, nearest first
+    pub fn nearest(
+        &self,
+        position: &Coordinate,
+        k: usize,
+        filter: &StyleFilter,
+    ) -> Vec<Neighbor<'a>> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        // Every waypoint within the radius is found, so once there are `k`
+        // of them, the closest `k` are the overall nearest
+        let mut radius = CELL_SIZE * METERS_PER_DEGREE;
+        loop {
+            let mut neighbors = self.within_radius(position, &Distance::Meters(radius), filter);
+            if neighbors.len() >= k || radius >= MAX_DISTANCE {
+                neighbors.truncate(k);
+                return neighbors;
+            }
+            radius *= 4.0;
+        }
+    }
+
+    /// Waypoints within `radius` of `position`, nearest first
+    pub fn within_radius(
+        &self,
+        position: &Coordinate,
+        radius: &Distance,
+        filter: &StyleFilter,
+    ) -> Vec<Neighbor<'a>> {
+        let (lat, lon) = position.position();
+        let radius = radius.to_meters();
+        let span = radius / METERS_PER_DEGREE;
+
+        let south = lat - span;
+        let north = lat + span;
+        let lon_span = match south > -90.0 && north < 90.0 {
+            true => span / south.abs().max(north.abs()).to_radians().cos(),
+            false => 180.0,
+        };
+
+        let mut neighbors = self
+            .candidates(south, north, lon - lon_span, lon + lon_span)
+            .into_iter()
+            .filter(|&index| filter.matches(self.waypoints[index].style))
+            .filter_map(|index| {
+                let waypoint = &self.waypoints[index];
+                let (meters, _) = MODEL.inverse((lat, lon), waypoint.position());
+                (meters <= radius).then_some(Neighbor {
+                    index,
+                    waypoint,
+                    distance: Distance::Meters(meters),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Grid cells are visited in hash order, so ties are broken by index
+        neighbors.sort_by(|a, b| {
+            let distance = a.distance.to_meters().total_cmp(&b.distance.to_meters());
+            distance.then(a.index.cmp(&b.index))
+        });
+        neighbors
+    }
+
+    /// Waypoints inside the bounding box from `south_west` to `north_east`,
+    /// in their original order
+    ///
+    /// The box crosses the antimeridian if `south_west` lies east of `north_east`.
+    pub fn within_bounds(
+        &self,
+        south_west: &Coordinate,
+        north_east: &Coordinate,
+        filter: &StyleFilter,
+    ) -> Vec<&'a Waypoint> {
+        let (south, west) = south_west.position();
+        let (north, mut east) = north_east.position();
+        if east < west {
+            east += 360.0;
+        }
+
+        let mut indices = self
+            .candidates(south, north, west, east)
+            .into_iter()
+            .filter(|&index| {
+                let waypoint = &self.waypoints[index];
+                let mut lon = waypoint.longitude;
+                if lon < west {
+                    lon += 360.0;
+                }
+                (south..=north).contains(&waypoint.latitude)
+                    && (west..=east).contains(&lon)
+                    && filter.matches(waypoint.style)
+            })
+            .collect::<Vec<_>>();
+
+        indices.sort_unstable();
+        indices
+            .into_iter()
+            .map(|index| &self.waypoints[index])
+            .collect()
+    }
+
+    /// Indices of the waypoints in all cells overlapping the given bounds
+    ///
+    /// `west` and `east` may exceed the range of -180 to 180 degrees.
+    fn candidates(&self, south: f64, north: f64, west: f64, east: f64) -> Vec<usize> {
+        let columns = (360.0 / CELL_SIZE).ceil() as i32;
+        let (south_row, west_column) = cell_of(south.max(-90.0), west);
+        let (north_row, _) = cell_of(north.min(90.0), west);
+        let width = (((east - west) / CELL_SIZE).ceil() as i32 + 1).min(columns - 1);
+
+        let rows = south_row..=north_row;
+        let in_range = |&(row, column): &(i32, i32)| {
+            rows.contains(&row) && (column - west_column).rem_euclid(columns) <= width
+        };
+
+        // Large areas are faster to check cell by cell than to look up
+        let area = (north_row - south_row + 1) as usize * (width + 1) as usize;
+        let cells: Vec<&Vec<usize>> = match area > self.cells.len() {
+            true => self
+                .cells
+                .iter()
+                .filter(|(cell, _)| in_range(cell))
+                .map(|(_, indices)| indices)
+                .collect(),
+            false => rows
+                .clone()
+                .flat_map(|row| (0..=width).map(move |i| (row, (west_column + i) % columns)))
+                .filter_map(|cell| self.cells.get(&cell))
+                .collect(),
+        };
+
+        cells.into_iter().flatten().copied().collect()
+    }
+}
+
+/// Grid cell (row, column) containing the position
+fn cell_of(latitude: f64, longitude: f64) -> (i32, i32) {
+    let columns = (360.0 / CELL_SIZE).ceil() as i32;
+    let row = ((latitude + 90.0) / CELL_SIZE).floor() as i32;
+    let column = ((longitude + 180.0) / CELL_SIZE).floor() as i32;
+    (row, column.rem_euclid(columns))
+}
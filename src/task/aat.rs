@@ -0,0 +1,103 @@
+use crate::task::TaskDistance;
+use crate::task::distance::{Goal, optimize};
+use crate::task::zone::ZoneGeometry;
+use crate::{Coordinate, Error, Task, TaskOptions, Waypoint};
+use std::time::Duration;
+
+/// Achievable distances of an assigned area task
+#[derive(Debug, Clone, PartialEq)]
+pub struct AatDistances {
+    /// Shortest path touching every area
+    pub minimum: TaskDistance,
+    /// Path through the centers of the areas
+    pub nominal: TaskDistance,
+    /// Longest path through every area
+    pub maximum: TaskDistance,
+    /// Minimum task time from [`TaskOptions::task_time`]
+    pub task_time: Option<Duration>,
+}
+
+impl AatDistances {
+    /// Average speed in km/h needed to fly the minimum distance within the task time
+    pub fn minimum_speed(&self) -> Option<f64> {
+        self.minimum.average_speed(self.task_time?)
+    }
+
+    /// Average speed in km/h needed to fly the maximum distance within the task time
+    pub fn maximum_speed(&self) -> Option<f64> {
+        self.maximum.average_speed(self.task_time?)
+    }
+}
+
+impl TaskDistance {
+    /// Average speed in km/h needed to cover the total distance in `time`
+    ///
+    /// Returns `None` for a zero duration.
+    pub fn average_speed(&self, time: Duration) -> Option<f64> {
+        if time.is_zero() {
+            return None;
+        }
+        Some(self.total.to_meters() / time.as_secs_f64() * 3.6)
+    }
+}
+
+impl Task {
+    /// Minimum, nominal and maximum distance through the areas of an assigned area task
+    ///
+    /// Start and finish are measured at their centers, only the areas of the
+    /// turnpoints in between are used to shorten or extend the course.
+    pub fn aat_distances(&self, waypoints: &[Waypoint]) -> Result<AatDistances, Error> {
+        let zones = self.aat_zones(waypoints)?;
+        let centers = zones.iter().map(ZoneGeometry::position).collect();
+
+        Ok(AatDistances {
+            minimum: TaskDistance::from_positions(optimize(&zones, Goal::Shortest)),
+            nominal: TaskDistance::from_positions(centers),
+            maximum: TaskDistance::from_positions(optimize(&zones, Goal::Longest)),
+            task_time: self.options.as_ref().and_then(TaskOptions::task_duration),
+        })
+    }
+
+    /// Distance through chosen target points, one for each turnpoint between
+    /// start and finish
+    ///
+    /// Returns [`Error::InvalidTarget`] if the number of targets doesn't match
+    /// or a target lies outside of its area.
+    pub fn aat_target_distance(
+        &self,
+        waypoints: &[Waypoint],
+        targets: &[Coordinate],
+    ) -> Result<TaskDistance, Error> {
+        let zones = self.aat_zones(waypoints)?;
+        let turnpoints = zones.len().saturating_sub(2);
+        if targets.len() != turnpoints {
+            let message = format!("expected {turnpoints} targets, got {}", targets.len());
+            return Err(Error::InvalidTarget(message));
+        }
+
+        let mut positions = zones.iter().map(ZoneGeometry::position).collect::<Vec<_>>();
+        for (i, target) in targets.iter().enumerate() {
+            let zone = &zones[i + 1];
+            if !zone.contains_position(target.position()) {
+                let name = &self.course_names()[i + 1];
+                let message = format!("{target} lies outside of the area of '{name}'");
+                return Err(Error::InvalidTarget(message));
+            }
+            positions[i + 1] = target.position();
+        }
+
+        Ok(TaskDistance::from_positions(positions))
+    }
+
+    /// Zone geometries with start and finish reduced to their centers
+    fn aat_zones(&self, waypoints: &[Waypoint]) -> Result<Vec<ZoneGeometry>, Error> {
+        let mut zones = self.zone_geometries(waypoints)?;
+        let last = zones.len().saturating_sub(1);
+        for i in [0, last] {
+            if let Some(zone) = zones.get_mut(i) {
+                *zone = ZoneGeometry::point(zone.position());
+            }
+        }
+        Ok(zones)
+    }
+}
@@ -0,0 +1,160 @@
+use crate::geodesy::EarthModel;
+use crate::task::TaskPoint;
+use crate::{Coordinate, Distance, Error, Task, TaskOptions, Waypoint, ZonePreset};
+
+const MODEL: EarthModel = EarthModel::Wgs84;
+
+/// Maximum distance between start and finish of a closed course in meters
+const CLOSED_TOLERANCE: f64 = 1000.0;
+/// Minimum triangle length in meters for the relaxed 25%/45% leg rule
+const LARGE_TRIANGLE: f64 = 750_000.0;
+/// Maximum distance of a start outside of the corners from the leg between
+/// the last and the first corner, as a fraction of the triangle length
+const START_ON_LEG_TOLERANCE: f64 = 0.05;
+
+/// Type of a task, as SeeYou determines it when the description is empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskKind {
+    /// Fixed course around all turnpoints in order
+    Racing,
+    /// Assigned area task with a minimum task time
+    AssignedArea,
+    /// Course chosen by the pilot, or fewer than two task points
+    Free,
+    /// Closed course around a single turnpoint
+    OutAndReturn,
+}
+
+/// Result of [`Task::classify()`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskClassification {
+    /// Type of the task
+    pub kind: TaskKind,
+    /// Triangle of a closed course with three corners
+    pub triangle: Option<Triangle>,
+}
+
+/// Closed triangle course and its FAI conformity
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triangle {
+    /// Corners of the triangle
+    pub corners: [Coordinate; 3],
+    /// Length of the legs between the corners, starting at the first corner
+    pub legs: [Distance; 3],
+    /// Sum of all legs
+    pub total: Distance,
+    /// Whether the legs satisfy the FAI triangle rule
+    ///
+    /// Every leg must be at least 28% of the total. Triangles of 750 km or
+    /// more only need 25% for the shortest leg, but no leg may exceed 45%.
+    pub fai: bool,
+}
+
+impl Task {
+    /// Determines the type of the task from its points and [`TaskOptions`]
+    ///
+    /// A task with a `TaskTime` is an assigned area task, while `RandomOrder`
+    /// or `MaxPts` let the pilot choose the course. Start and finish count as
+    /// the same point if they are less than 1 km apart.
+    ///
+    /// For closed courses with three corners (the start either being one of
+    /// the corners or lying on a leg) the triangle is measured along
+    /// [`Task::distance()`], except for assigned area tasks. A start on a
+    /// leg may be off by 5% of the triangle length, otherwise the course is
+    /// a quadrilateral without a triangle.
+    pub fn classify(&self, waypoints: &[Waypoint]) -> Result<TaskClassification, Error> {
+        let points = self.resolve(waypoints)?;
+        let closed = is_closed(&points);
+
+        let kind = if self.is_free() || points.len() < 2 {
+            TaskKind::Free
+        } else if self.is_assigned_area(&points) {
+            TaskKind::AssignedArea
+        } else if closed && points.len() == 3 {
+            TaskKind::OutAndReturn
+        } else {
+            TaskKind::Racing
+        };
+
+        let corners = match points.len() {
+            4 => 0..3,
+            5 => 1..4,
+            _ => 0..0,
+        };
+        let triangle = if closed && !corners.is_empty() && kind != TaskKind::AssignedArea {
+            let distance = self.distance(waypoints)?;
+            let start = distance.points[0].position();
+            let corners = &distance.points[corners];
+            let triangle = Triangle::new([corners[0], corners[1], corners[2]]);
+
+            let closing_leg = (corners[2].position(), corners[0].position());
+            let off_leg = MODEL.distance_to_segment(start, closing_leg.0, closing_leg.1);
+            let tolerance = START_ON_LEG_TOLERANCE * triangle.total.to_meters();
+            (off_leg <= tolerance).then_some(triangle)
+        } else {
+            None
+        };
+
+        Ok(TaskClassification { kind, triangle })
+    }
+
+    fn is_free(&self) -> bool {
+        let options = self.options.as_ref();
+        options
+            .is_some_and(|options| options.random_order == Some(true) || options.max_pts.is_some())
+    }
+
+    /// A valid non-zero `TaskTime`, or turnpoints with fixed area sectors
+    fn is_assigned_area(&self, points: &[TaskPoint<'_>]) -> bool {
+        let options = self.options.as_ref();
+        if options
+            .and_then(TaskOptions::task_duration)
+            .is_some_and(|time| !time.is_zero())
+        {
+            return true;
+        }
+
+        let mut turnpoints = points.iter().skip(1).take(points.len().saturating_sub(2));
+        turnpoints.any(|point| {
+            let preset = point.observation_zone.and_then(|oz| oz.classify());
+            matches!(preset, Some(ZonePreset::FixedSector { .. }))
+        })
+    }
+}
+
+fn is_closed(points: &[TaskPoint<'_>]) -> bool {
+    match points {
+        [start, _, .., finish] => {
+            let start = start.waypoint.position();
+            let finish = finish.waypoint.position();
+            MODEL.inverse(start, finish).0 < CLOSED_TOLERANCE
+        }
+        _ => false,
+    }
+}
+
+impl Triangle {
+    fn new(corners: [Coordinate; 3]) -> Self {
+        let legs = [0, 1, 2].map(|i| {
+            let from = corners[i].position();
+            let to = corners[(i + 1) % 3].position();
+            MODEL.inverse(from, to).0
+        });
+
+        let total = legs.iter().sum::<f64>();
+        let shortest = legs.iter().copied().fold(f64::INFINITY, f64::min);
+        let longest = legs.iter().copied().fold(0.0, f64::max);
+        let fai = if total >= LARGE_TRIANGLE {
+            shortest >= 0.25 * total && longest <= 0.45 * total
+        } else {
+            shortest >= 0.28 * total
+        };
+
+        Self {
+            corners,
+            legs: legs.map(Distance::Meters),
+            total: Distance::Meters(total),
+            fai,
+        }
+    }
+}
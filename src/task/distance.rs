@@ -0,0 +1,198 @@
+use crate::geodesy::EarthModel;
+use crate::task::zone::ZoneGeometry;
+use crate::{Coordinate, Distance, Error, Task, Waypoint};
+
+const MODEL: EarthModel = EarthModel::Wgs84;
+
+/// Number of samples per outline curve before refining the best one
+const OUTLINE_SAMPLES: usize = 48;
+/// Number of golden-section steps used to refine the best sample
+const REFINE_STEPS: usize = 40;
+/// Maximum number of passes over all zones
+const MAX_PASSES: usize = 50;
+/// Minimum improvement in meters for another pass over all zones
+const CONVERGENCE: f64 = 0.001;
+
+/// Length of a task and its legs
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskDistance {
+    /// Points the distance is measured through, one per task point
+    pub points: Vec<Coordinate>,
+    /// Length of each leg between consecutive points
+    pub legs: Vec<Distance>,
+    /// Sum of all legs
+    pub total: Distance,
+}
+
+impl Task {
+    /// Task distance as configured by [`TaskOptions::wp_dis`](crate::TaskOptions::wp_dis)
+    ///
+    /// Returns [`Task::nominal_distance()`] if `WpDis=True`, and
+    /// [`Task::optimized_distance()`] otherwise.
+    pub fn distance(&self, waypoints: &[Waypoint]) -> Result<TaskDistance, Error> {
+        let options = self.options.as_ref();
+        if options.and_then(|options| options.wp_dis).unwrap_or(false) {
+            self.nominal_distance(waypoints)
+        } else {
+            self.optimized_distance(waypoints)
+        }
+    }
+
+    /// Distance between the centers of the task points
+    pub fn nominal_distance(&self, waypoints: &[Waypoint]) -> Result<TaskDistance, Error> {
+        let points = self.resolve(waypoints)?;
+        let positions = points.iter().map(|p| p.waypoint.position()).collect();
+        Ok(TaskDistance::from_positions(positions))
+    }
+
+    /// Shortest distance that touches the observation zone of every task point
+    ///
+    /// Points without an observation zone or radius are used as they are.
+    pub fn optimized_distance(&self, waypoints: &[Waypoint]) -> Result<TaskDistance, Error> {
+        let points = self.resolve(waypoints)?;
+        let zones = ZoneGeometry::for_task(&points);
+        Ok(TaskDistance::from_positions(optimize(
+            &zones,
+            Goal::Shortest,
+        )))
+    }
+}
+
+impl TaskDistance {
+    pub(crate) fn from_positions(positions: Vec<(f64, f64)>) -> Self {
+        let legs = positions
+            .windows(2)
+            .map(|leg| MODEL.inverse(leg[0], leg[1]).0)
+            .collect::<Vec<_>>();
+
+        let total = Distance::Meters(legs.iter().sum());
+        let legs = legs.into_iter().map(Distance::Meters).collect();
+        let points = positions
+            .into_iter()
+            .map(|(lat, lon)| Coordinate::new_unchecked(lat, lon))
+            .collect();
+
+        Self {
+            points,
+            legs,
+            total,
+        }
+    }
+}
+
+/// Whether [`optimize()`] looks for the shortest or the longest path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Goal {
+    Shortest,
+    Longest,
+}
+
+impl Goal {
+    /// Turns a path length into a score where lower is better
+    fn score(self, length: f64) -> f64 {
+        match self {
+            Goal::Shortest => length,
+            Goal::Longest => -length,
+        }
+    }
+}
+
+/// Finds the positions inside the zones with the shortest (or longest)
+/// total path by repeatedly moving each position to the best spot between
+/// its neighbours
+pub(crate) fn optimize(zones: &[ZoneGeometry], goal: Goal) -> Vec<(f64, f64)> {
+    let mut positions = zones.iter().map(ZoneGeometry::position).collect::<Vec<_>>();
+    if zones.len() < 2 {
+        return positions;
+    }
+
+    let mut total = goal.score(path_length(&positions));
+    for _ in 0..MAX_PASSES {
+        for (i, zone) in zones.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|i| positions[i]);
+            let next = positions.get(i + 1).copied();
+            positions[i] = best_position(zone, previous, next, goal);
+        }
+
+        let new_total = goal.score(path_length(&positions));
+        let improvement = total - new_total;
+        total = new_total;
+        if improvement < CONVERGENCE {
+            break;
+        }
+    }
+
+    positions
+}
+
+fn path_length(positions: &[(f64, f64)]) -> f64 {
+    let legs = positions.windows(2);
+    legs.map(|leg| MODEL.inverse(leg[0], leg[1]).0).sum()
+}
+
+/// Position inside `zone` with the best path from `previous` to `next`
+fn best_position(
+    zone: &ZoneGeometry,
+    previous: Option<(f64, f64)>,
+    next: Option<(f64, f64)>,
+    goal: Goal,
+) -> (f64, f64) {
+    if zone.is_point() {
+        return zone.position();
+    }
+
+    let cost = |position: (f64, f64)| {
+        let to_previous = previous.map_or(0.0, |p| MODEL.inverse(p, position).0);
+        let to_next = next.map_or(0.0, |n| MODEL.inverse(position, n).0);
+        goal.score(to_previous + to_next)
+    };
+
+    let mut best = (zone.position(), cost(zone.position()));
+    let mut consider = |position: (f64, f64)| {
+        let cost = cost(position);
+        if cost < best.1 {
+            best = (position, cost);
+        }
+    };
+
+    // A neighbour inside the zone can't be beaten, since any detour is longer
+    if !zone.is_line() && goal == Goal::Shortest {
+        for neighbour in [previous, next].into_iter().flatten() {
+            if zone.contains_position(neighbour) {
+                consider(neighbour);
+            }
+        }
+    }
+
+    for curve in zone.outline() {
+        let at = |t: f64| zone.position_on(&curve, t);
+
+        let step = 1.0 / OUTLINE_SAMPLES as f64;
+        let (best_t, _) = (0..=OUTLINE_SAMPLES)
+            .map(|i| i as f64 * step)
+            .map(|t| (t, cost(at(t))))
+            .fold((0.0, f64::INFINITY), |a, b| if b.1 < a.1 { b } else { a });
+
+        // Golden-section search around the best sample
+        let (mut low, mut high) = (best_t - step, best_t + step);
+        if !curve.is_closed() {
+            (low, high) = (low.max(0.0), high.min(1.0));
+        }
+
+        let ratio = (5f64.sqrt() - 1.0) / 2.0;
+        for _ in 0..REFINE_STEPS {
+            let t1 = high - ratio * (high - low);
+            let t2 = low + ratio * (high - low);
+            if cost(at(t1)) < cost(at(t2)) {
+                high = t2;
+            } else {
+                low = t1;
+            }
+        }
+
+        consider(at(best_t));
+        consider(at((low + high) / 2.0));
+    }
+
+    best.0
+}
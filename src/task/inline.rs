@@ -0,0 +1,106 @@
+use crate::geodesy::EarthModel;
+use crate::merge::unique_name;
+use crate::{CupFile, Distance, Error, Task, Waypoint};
+use std::collections::HashSet;
+
+const MODEL: EarthModel = EarthModel::Wgs84;
+
+impl CupFile {
+    /// Moves the inline `Point=` definitions of all tasks into
+    /// [`CupFile::waypoints`] and lets the tasks refer to them by name
+    ///
+    /// An inline point reuses an existing waypoint with the same name, or
+    /// with any name at the same position, if the two lie within
+    /// `tolerance` of each other. Otherwise it is added to the waypoints,
+    /// with a numeric suffix like `Name (2)` if its name is already taken.
+    /// Returns the names of the added waypoints.
+    pub fn promote_inline_points(&mut self, tolerance: &Distance) -> Vec<String> {
+        let tolerance = tolerance.to_meters();
+        let mut names = self
+            .waypoints
+            .iter()
+            .map(|waypoint| waypoint.name.clone())
+            .collect::<HashSet<_>>();
+        let mut added = Vec::new();
+
+        for task in &mut self.tasks {
+            let (points, rest) = std::mem::take(&mut task.points)
+                .into_iter()
+                .partition::<Vec<_>, _>(|(index, _)| (*index as usize) < task.waypoint_names.len());
+            task.points = rest;
+
+            for (index, point) in points {
+                let close = |waypoint: &&Waypoint| {
+                    let (meters, _) = MODEL.inverse(waypoint.position(), point.position());
+                    meters <= tolerance
+                };
+                let same_name = self
+                    .waypoints
+                    .iter()
+                    .filter(close)
+                    .find(|w| w.name == point.name);
+                let same_position = || self.waypoints.iter().find(close);
+
+                let name = match same_name.or_else(same_position) {
+                    Some(waypoint) => waypoint.name.clone(),
+                    None => {
+                        let mut name = point.name.clone();
+                        if names.contains(&name) {
+                            name = unique_name(&name, &names);
+                        }
+                        names.insert(name.clone());
+                        added.push(name.clone());
+                        self.waypoints.push(Waypoint {
+                            name: name.clone(),
+                            ..point
+                        });
+                        name
+                    }
+                };
+                task.waypoint_names[index as usize] = name;
+            }
+        }
+
+        added
+    }
+
+    /// Turns every waypoint reference of all tasks into an inline `Point=`
+    /// definition
+    ///
+    /// See [`Task::inline_waypoints()`].
+    pub fn inline_task_points(&mut self) -> Result<(), Error> {
+        for task in &mut self.tasks {
+            task.inline_waypoints(&self.waypoints)?;
+        }
+        Ok(())
+    }
+}
+
+impl Task {
+    /// Adds an inline `Point=` definition for every entry of
+    /// [`Task::waypoint_names`] that refers to `waypoints`, so the task no
+    /// longer depends on the waypoint list
+    ///
+    /// Empty takeoff and landing names are skipped. Alternative start
+    /// points in [`Task::multiple_starts`] can't be defined inline and are
+    /// left unchanged. Fails if a name isn't found in `waypoints`.
+    pub fn inline_waypoints(&mut self, waypoints: &[Waypoint]) -> Result<(), Error> {
+        let mut points = Vec::new();
+        for (index, name) in self.waypoint_names.iter().enumerate() {
+            let inline = self.points.iter().any(|(idx, _)| *idx as usize == index);
+            if inline || name.is_empty() {
+                continue;
+            }
+
+            let waypoint = waypoints
+                .iter()
+                .find(|waypoint| waypoint.name == *name)
+                .ok_or_else(|| Error::UnknownWaypoint(name.clone()))?;
+            points.push((index as u32, waypoint.clone()));
+        }
+
+        self.points.extend(points);
+        self.points.sort_by_key(|(index, _)| *index);
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+mod aat;
+mod classify;
+mod distance;
+mod inline;
+mod presets;
+mod references;
+mod resolve;
+mod validate;
+mod zone;
+
+pub use aat::*;
+pub use classify::*;
+pub use distance::*;
+pub use presets::ZonePreset;
+pub use resolve::*;
+pub use validate::*;
+pub use zone::ZoneGeometry;
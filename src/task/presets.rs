@@ -0,0 +1,198 @@
+use crate::{Distance, ObsZoneStyle, ObservationZone};
+
+/// Tolerance for comparing radii in meters
+const RADIUS_TOLERANCE: f64 = 0.5;
+/// Tolerance for comparing angles in degrees
+const ANGLE_TOLERANCE: f64 = 1e-6;
+
+/// Well-known observation zone shape
+///
+/// See [`ObservationZone::classify()`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZonePreset {
+    /// Cylinder around the task point
+    Cylinder { radius: Distance },
+    /// Line of the given total length, perpendicular to the task
+    Line { length: Distance },
+    /// 90° sector on the outside of the turn
+    FaiSector { radius: Distance },
+    /// 90° sector on the outside of the turn, combined with a cylinder
+    /// (e.g. the DAeC keyhole with 10 km and 500 m)
+    Keyhole {
+        sector_radius: Distance,
+        cylinder_radius: Distance,
+    },
+    /// BGA enhanced option zone: 10 km 180° sector combined with a 500 m cylinder
+    BgaEnhancedOption,
+    /// Sector with a fixed direction, typically used as area of an assigned area task
+    FixedSector {
+        radius: Distance,
+        /// Bearing of the sector bisector in degrees
+        direction: f64,
+        /// Half of the opening angle in degrees
+        half_angle: f64,
+    },
+}
+
+impl ObservationZone {
+    /// FAI turnpoint sector: 90° sector on the outside of the turn
+    ///
+    /// The FAI sector has no defined length, so the `radius` depends on the
+    /// task and the flight recorder evaluation.
+    pub fn fai_sector(index: u32, radius: Distance) -> Self {
+        Self::sector(index, ObsZoneStyle::Symmetrical, radius, 45.0)
+    }
+
+    /// Cylinder with the given `radius`
+    pub fn cylinder(index: u32, radius: Distance) -> Self {
+        Self::sector(index, ObsZoneStyle::Symmetrical, radius, 180.0)
+    }
+
+    /// Line with the given total `length`
+    ///
+    /// Lines at the start (`index == 0`) are perpendicular to the first leg,
+    /// all others are perpendicular to the incoming leg.
+    pub fn line(index: u32, length: Distance) -> Self {
+        let style = match index {
+            0 => ObsZoneStyle::ToNextPoint,
+            _ => ObsZoneStyle::ToPreviousPoint,
+        };
+        let radius = Distance::Meters(length.to_meters() / 2.0);
+
+        Self {
+            line: Some(true),
+            ..Self::sector(index, style, radius, 180.0)
+        }
+    }
+
+    /// 90° sector on the outside of the turn with `sector_radius`, combined
+    /// with a cylinder of `cylinder_radius`
+    pub fn keyhole(index: u32, sector_radius: Distance, cylinder_radius: Distance) -> Self {
+        Self {
+            r2: Some(cylinder_radius),
+            a2: Some(180.0),
+            ..Self::sector(index, ObsZoneStyle::Symmetrical, sector_radius, 45.0)
+        }
+    }
+
+    /// DAeC keyhole: 10 km 90° sector combined with a 500 m cylinder
+    pub fn daec_keyhole(index: u32) -> Self {
+        Self::keyhole(index, Distance::Kilometers(10.0), Distance::Meters(500.0))
+    }
+
+    /// BGA fixed course zone: 20 km 90° sector combined with a 500 m cylinder
+    pub fn bga_fixed_course(index: u32) -> Self {
+        Self::keyhole(index, Distance::Kilometers(20.0), Distance::Meters(500.0))
+    }
+
+    /// BGA enhanced option zone: 10 km 180° sector combined with a 500 m cylinder
+    pub fn bga_enhanced_option(index: u32) -> Self {
+        Self {
+            r2: Some(Distance::Meters(500.0)),
+            a2: Some(180.0),
+            ..Self::sector(
+                index,
+                ObsZoneStyle::Symmetrical,
+                Distance::Kilometers(10.0),
+                90.0,
+            )
+        }
+    }
+
+    /// Assigned area sector with a fixed `direction` (bearing of the bisector)
+    /// and `half_angle`
+    ///
+    /// A `half_angle` of 180° results in a full cylinder.
+    pub fn aat_sector(index: u32, radius: Distance, direction: f64, half_angle: f64) -> Self {
+        Self {
+            a12: Some(direction),
+            ..Self::sector(index, ObsZoneStyle::Fixed, radius, half_angle)
+        }
+    }
+
+    fn sector(index: u32, style: ObsZoneStyle, radius: Distance, half_angle: f64) -> Self {
+        Self {
+            index,
+            style,
+            r1: Some(radius),
+            a1: Some(half_angle),
+            r2: None,
+            a2: None,
+            a12: None,
+            line: None,
+        }
+    }
+
+    /// Recognises the well-known zone shapes created by the preset constructors
+    ///
+    /// Missing angles are treated as 180°. Returns `None` for any other shape.
+    pub fn classify(&self) -> Option<ZonePreset> {
+        let r1 = self
+            .r1
+            .as_ref()
+            .map(Distance::to_meters)
+            .filter(|r| *r > 0.0)?;
+        let r2 = self.r2.as_ref().map(Distance::to_meters).unwrap_or(0.0);
+        let a1 = self.a1.unwrap_or(180.0);
+        let a2 = self.a2.unwrap_or(180.0);
+        let symmetrical = self.style == ObsZoneStyle::Symmetrical;
+
+        if self.line == Some(true) {
+            let length = Distance::Meters(r1 * 2.0);
+            return Some(ZonePreset::Line { length });
+        }
+
+        if r2 > 0.0 && r2 < r1 {
+            if !symmetrical || !angle_eq(a2, 180.0) {
+                return None;
+            }
+            if angle_eq(a1, 45.0) {
+                let sector_radius = Distance::Meters(r1);
+                let cylinder_radius = Distance::Meters(r2);
+                return Some(ZonePreset::Keyhole {
+                    sector_radius,
+                    cylinder_radius,
+                });
+            }
+            if angle_eq(a1, 90.0) && radius_eq(r1, 10_000.0) && radius_eq(r2, 500.0) {
+                return Some(ZonePreset::BgaEnhancedOption);
+            }
+            return None;
+        }
+
+        // A sector within a larger cylinder is just the cylinder
+        if r2 > 0.0 {
+            if a2 < 180.0 {
+                return None;
+            }
+            let radius = Distance::Meters(r2);
+            return Some(ZonePreset::Cylinder { radius });
+        }
+
+        let radius = Distance::Meters(r1);
+        if a1 >= 180.0 {
+            return Some(ZonePreset::Cylinder { radius });
+        }
+        if symmetrical && angle_eq(a1, 45.0) {
+            return Some(ZonePreset::FaiSector { radius });
+        }
+        if let (ObsZoneStyle::Fixed, Some(direction)) = (self.style, self.a12) {
+            let half_angle = a1;
+            return Some(ZonePreset::FixedSector {
+                radius,
+                direction,
+                half_angle,
+            });
+        }
+
+        None
+    }
+}
+
+fn angle_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < ANGLE_TOLERANCE
+}
+
+fn radius_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < RADIUS_TOLERANCE
+}
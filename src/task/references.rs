@@ -0,0 +1,83 @@
+use crate::Task;
+
+impl Task {
+    /// Replaces the waypoint names the task refers to
+    ///
+    /// `rename` is called for every name in [`Task::waypoint_names`] and
+    /// [`Task::multiple_starts`] and returns the new name, or `None` to keep
+    /// it. Names of inline `Point=` definitions are left untouched, since
+    /// they don't refer to the waypoint list.
+    pub(crate) fn rename_references(&mut self, mut rename: impl FnMut(&str) -> Option<String>) {
+        for (index, name) in self.waypoint_names.iter_mut().enumerate() {
+            let inline = self.points.iter().any(|(idx, _)| *idx as usize == index);
+            if let Some(new_name) = rename(name).filter(|_| !inline) {
+                *name = new_name;
+            }
+        }
+
+        for name in &mut self.multiple_starts {
+            if let Some(new_name) = rename(name) {
+                *name = new_name;
+            }
+        }
+    }
+
+    /// Removes the entries of [`Task::waypoint_names`] for which `keep`
+    /// returns false, together with their observation zones and inline
+    /// `Point=` definitions
+    ///
+    /// Removed takeoff and landing entries are replaced by an empty name.
+    /// The indices of the remaining observation zones and inline points are
+    /// updated to their new positions.
+    pub(crate) fn remove_points(&mut self, mut keep: impl FnMut(usize, &str) -> bool) {
+        let last = self.waypoint_names.len().saturating_sub(1);
+        let mut new_indices = Vec::with_capacity(self.waypoint_names.len());
+        let mut names = Vec::with_capacity(self.waypoint_names.len());
+
+        for (index, name) in self.waypoint_names.drain(..).enumerate() {
+            let kept = keep(index, &name);
+            if kept || index == 0 || index == last {
+                new_indices.push(kept.then_some(names.len()));
+                names.push(if kept { name } else { String::new() });
+            } else {
+                new_indices.push(None);
+            }
+        }
+        self.waypoint_names = names;
+
+        // `Point=` indices count from the takeoff, `ObsZone=` indices from the start
+        self.points.retain_mut(|(index, _)| {
+            let new_index = new_indices.get(*index as usize).copied().flatten();
+            new_index
+                .inspect(|new_index| *index = *new_index as u32)
+                .is_some()
+        });
+        self.observation_zones.retain_mut(|zone| {
+            let new_index = new_indices.get(zone.index as usize + 1).copied().flatten();
+            new_index
+                .filter(|new_index| *new_index > 0)
+                .inspect(|new_index| zone.index = *new_index as u32 - 1)
+                .is_some()
+        });
+    }
+
+    /// Whether the task refers to the waypoint list entry named `name`
+    pub(crate) fn refers_to(&self, name: &str) -> bool {
+        !self.reference_indices(name).is_empty()
+            || self.multiple_starts.iter().any(|start| start == name)
+    }
+
+    /// Indices of [`Task::waypoint_names`] referring to the waypoint list
+    /// entry named `name`, excluding inline `Point=` definitions
+    pub(crate) fn reference_indices(&self, name: &str) -> Vec<usize> {
+        self.waypoint_names
+            .iter()
+            .enumerate()
+            .filter(|(index, entry)| {
+                let inline = self.points.iter().any(|(idx, _)| *idx as usize == *index);
+                *entry == name && !inline
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
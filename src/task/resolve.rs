@@ -0,0 +1,54 @@
+use crate::{Error, ObservationZone, Task, Waypoint};
+
+/// Task point resolved against the waypoints of a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskPoint<'a> {
+    /// Consecutive number of the point (0 = Start), matching [`ObservationZone::index`]
+    pub index: u32,
+    /// Waypoint of the point, either from the waypoint list or an inline `Point=` definition
+    pub waypoint: &'a Waypoint,
+    /// Observation zone of the point, if defined
+    pub observation_zone: Option<&'a ObservationZone>,
+}
+
+impl Task {
+    /// Names of the task points from start to finish
+    ///
+    /// The first and last entries of [`Task::waypoint_names`] are the takeoff
+    /// and landing, which are not part of the task itself.
+    pub fn course_names(&self) -> &[String] {
+        match self.waypoint_names.len() {
+            0..=2 => &[],
+            len => &self.waypoint_names[1..len - 1],
+        }
+    }
+
+    /// Resolves the task points from start to finish
+    ///
+    /// Inline `Point=` definitions in [`Task::points`] take precedence over
+    /// waypoints with the same name in `waypoints`.
+    pub fn resolve<'a>(&'a self, waypoints: &'a [Waypoint]) -> Result<Vec<TaskPoint<'a>>, Error> {
+        self.course_names()
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let index = index as u32;
+
+                // `Point=` indices count from the takeoff
+                let inline = self.points.iter().find(|(idx, _)| *idx == index + 1);
+                let waypoint = inline
+                    .map(|(_, waypoint)| waypoint)
+                    .or_else(|| waypoints.iter().find(|waypoint| waypoint.name == *name))
+                    .ok_or_else(|| Error::UnknownWaypoint(name.clone()))?;
+
+                let observation_zone = self.observation_zones.iter().find(|oz| oz.index == index);
+
+                Ok(TaskPoint {
+                    index,
+                    waypoint,
+                    observation_zone,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{CupFile, Distance, DuplicateNames, ObservationZone, Task, TaskOptions};
+use std::fmt::{Display, Formatter};
+
+/// Semantic problem of a task, found by [`Task::validate()`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskIssue {
+    /// The task has fewer than two points between takeoff and landing
+    TooFewPoints { count: usize },
+    /// `BeforePts` and `AfterPts` together exceed `MaxPts`
+    MandatoryPointsExceedMaximum { before: u32, after: u32, max: u32 },
+    /// `RandomOrder=True` without `MaxPts`
+    RandomOrderWithoutMaxPoints,
+    /// An observation zone refers to a point that is not part of the task
+    ZoneOutOfRange { zone: u32 },
+    /// A line also has a sector angle other than 180°
+    LineWithAngle { zone: u32, angle: f64 },
+    /// `R1` or `R2` is negative
+    NegativeRadius {
+        zone: u32,
+        field: &'static str,
+        radius: Distance,
+    },
+    /// `A1` or `A2` is not between 0° and 180°
+    AngleOutOfRange {
+        zone: u32,
+        field: &'static str,
+        angle: f64,
+    },
+    /// A task point is neither a waypoint of the file nor defined inline,
+    /// only reported by [`CupFile::validate()`]
+    UnknownWaypoint { name: String },
+}
+
+impl Display for TaskIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskIssue::TooFewPoints { count } => {
+                write!(f, "Task needs at least 2 points, found {count}")
+            }
+            TaskIssue::MandatoryPointsExceedMaximum { before, after, max } => write!(
+                f,
+                "BeforePts={before} and AfterPts={after} exceed MaxPts={max}"
+            ),
+            TaskIssue::RandomOrderWithoutMaxPoints => {
+                write!(f, "RandomOrder requires MaxPts")
+            }
+            TaskIssue::ZoneOutOfRange { zone } => {
+                write!(f, "ObsZone={zone} does not refer to a task point")
+            }
+            TaskIssue::LineWithAngle { zone, angle } => {
+                write!(f, "ObsZone={zone} is a line but has A1={angle}")
+            }
+            TaskIssue::NegativeRadius {
+                zone,
+                field,
+                radius,
+            } => write!(f, "ObsZone={zone} has negative {field}={radius}"),
+            TaskIssue::AngleOutOfRange { zone, field, angle } => write!(
+                f,
+                "ObsZone={zone} has {field}={angle} (must be between 0 and 180)"
+            ),
+            TaskIssue::UnknownWaypoint { name } => write!(f, "Unknown waypoint: '{name}'"),
+        }
+    }
+}
+
+/// [`TaskIssue`] of a task in a file, found by [`CupFile::validate()`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskDiagnostic {
+    /// Index of the task in [`CupFile::tasks`]
+    pub task: usize,
+    /// Description of the task, if any
+    pub description: Option<String>,
+    /// Problem found in the task
+    pub issue: TaskIssue,
+}
+
+impl Display for TaskDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.description {
+            Some(description) => write!(f, "Task '{description}': {}", self.issue),
+            None => write!(f, "Task #{}: {}", self.task + 1, self.issue),
+        }
+    }
+}
+
+/// Problem of a file, found by [`CupFile::validate()`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileDiagnostic {
+    /// Several waypoints share a name, so that task points referring to it
+    /// are ambiguous
+    DuplicateName(DuplicateNames),
+    /// Problem of a task
+    Task(TaskDiagnostic),
+}
+
+impl Display for FileDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileDiagnostic::DuplicateName(duplicates) => {
+                let count = duplicates.indices.len();
+                let name = &duplicates.name;
+                write!(f, "Waypoint name used {count} times: '{name}'")
+            }
+            FileDiagnostic::Task(diagnostic) => diagnostic.fmt(f),
+        }
+    }
+}
+
+impl Task {
+    /// Checks the task against the rules of the file format
+    ///
+    /// Returns an empty list if no problems were found. Lines with `A1=180`
+    /// are accepted, since SeeYou writes them that way.
+    pub fn validate(&self) -> Vec<TaskIssue> {
+        let mut issues = Vec::new();
+
+        let count = self.course_names().len();
+        if count < 2 {
+            issues.push(TaskIssue::TooFewPoints { count });
+        }
+
+        if let Some(options) = &self.options {
+            validate_options(options, &mut issues);
+        }
+
+        for zone in &self.observation_zones {
+            if zone.index as usize >= count {
+                issues.push(TaskIssue::ZoneOutOfRange { zone: zone.index });
+            }
+            validate_zone(zone, &mut issues);
+        }
+
+        issues
+    }
+}
+
+impl CupFile {
+    /// Checks that waypoint names are unique, validates all tasks with
+    /// [`Task::validate()`] and checks that every task point refers to a
+    /// waypoint of the file
+    pub fn validate(&self) -> Vec<FileDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let duplicates = self.index().duplicates().to_vec();
+        diagnostics.extend(duplicates.into_iter().map(FileDiagnostic::DuplicateName));
+
+        for (index, task) in self.tasks.iter().enumerate() {
+            let mut issues = task.validate();
+
+            for (i, name) in task.course_names().iter().enumerate() {
+                // `Point=` indices count from the takeoff
+                let inline = task.points.iter().any(|(idx, _)| *idx as usize == i + 1);
+                let known = self.waypoints.iter().any(|waypoint| waypoint.name == *name);
+                if !inline && !known {
+                    let name = name.clone();
+                    issues.push(TaskIssue::UnknownWaypoint { name });
+                }
+            }
+
+            diagnostics.extend(issues.into_iter().map(|issue| {
+                FileDiagnostic::Task(TaskDiagnostic {
+                    task: index,
+                    description: task.description.clone(),
+                    issue,
+                })
+            }));
+        }
+
+        diagnostics
+    }
+}
+
+fn validate_options(options: &TaskOptions, issues: &mut Vec<TaskIssue>) {
+    if let Some(max) = options.max_pts {
+        let before = options.before_pts.unwrap_or(0);
+        let after = options.after_pts.unwrap_or(0);
+        if before.saturating_add(after) > max {
+            issues.push(TaskIssue::MandatoryPointsExceedMaximum { before, after, max });
+        }
+    } else if options.random_order == Some(true) {
+        issues.push(TaskIssue::RandomOrderWithoutMaxPoints);
+    }
+}
+
+fn validate_zone(zone: &ObservationZone, issues: &mut Vec<TaskIssue>) {
+    let index = zone.index;
+
+    let line_angle = zone.a1.filter(|a1| *a1 != 180.0);
+    if let (Some(true), Some(angle)) = (zone.line, line_angle) {
+        issues.push(TaskIssue::LineWithAngle { zone: index, angle });
+    }
+
+    for (field, radius) in [("R1", &zone.r1), ("R2", &zone.r2)] {
+        if let Some(radius) = radius.as_ref().filter(|r| r.to_meters() < 0.0) {
+            issues.push(TaskIssue::NegativeRadius {
+                zone: index,
+                field,
+                radius: radius.clone(),
+            });
+        }
+    }
+
+    for (field, angle) in [("A1", zone.a1), ("A2", zone.a2)] {
+        if let Some(angle) = angle.filter(|angle| !(0.0..=180.0).contains(angle)) {
+            issues.push(TaskIssue::AngleOutOfRange {
+                zone: index,
+                field,
+                angle,
+            });
+        }
+    }
+}
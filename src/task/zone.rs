@@ -0,0 +1,399 @@
+use crate::geodesy::EarthModel;
+use crate::task::TaskPoint;
+use crate::{Coordinate, Error, ObsZoneStyle, ObservationZone, Task, Waypoint};
+
+const MODEL: EarthModel = EarthModel::Wgs84;
+
+/// Angular resolution of [`ZoneGeometry::polygon()`] in degrees
+const POLYGON_STEP: f64 = 2.0;
+
+/// Observation zone placed at a task point and oriented along the task
+///
+/// Created by [`ObservationZone::geometry()`] or [`Task::zone_geometries()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneGeometry {
+    /// Position of the task point
+    center: (f64, f64),
+    /// Bearing of the zone bisector, pointing from the center into the zone
+    direction: f64,
+    /// Outer radius in meters
+    r1: f64,
+    /// Half-angle of the outer sector in degrees
+    a1: f64,
+    /// Inner radius in meters
+    r2: f64,
+    /// Half-angle of the inner sector in degrees
+    a2: f64,
+    /// Whether the zone is a line of length `2 * r1` perpendicular to `direction`
+    line: bool,
+}
+
+/// Piece of a zone outline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Curve {
+    /// Arc with `radius` from bearing `start` clockwise over `sweep` degrees
+    Arc { radius: f64, start: f64, sweep: f64 },
+    /// Straight segment along `bearing`, from signed distance `from` to `to`
+    Radial { bearing: f64, from: f64, to: f64 },
+}
+
+impl Task {
+    /// Geometry of the observation zone of every task point, from start to finish
+    ///
+    /// Task points without an observation zone are returned as zones
+    /// without any extent.
+    pub fn zone_geometries(&self, waypoints: &[Waypoint]) -> Result<Vec<ZoneGeometry>, Error> {
+        Ok(ZoneGeometry::for_task(&self.resolve(waypoints)?))
+    }
+}
+
+impl ObservationZone {
+    /// Places the zone at `point` and orients it according to its [`ObsZoneStyle`]
+    ///
+    /// `previous`, `next` and `start` are the positions of the neighbouring
+    /// task points and the start point, if there are any. For
+    /// [`ObsZoneStyle::Fixed`], [`ObservationZone::a12`] is the bearing of the
+    /// zone bisector.
+    pub fn geometry(
+        &self,
+        point: &Coordinate,
+        previous: Option<&Coordinate>,
+        next: Option<&Coordinate>,
+        start: Option<&Coordinate>,
+    ) -> ZoneGeometry {
+        ZoneGeometry::new(
+            self,
+            point.position(),
+            previous.map(Coordinate::position),
+            next.map(Coordinate::position),
+            start.map(Coordinate::position),
+        )
+    }
+}
+
+impl ZoneGeometry {
+    /// Position of the task point
+    pub fn center(&self) -> Coordinate {
+        let (lat, lon) = self.center;
+        Coordinate::new_unchecked(lat, lon)
+    }
+
+    /// Bearing in degrees of the zone bisector, pointing from the center into the zone
+    ///
+    /// Lines are perpendicular to this direction.
+    pub fn direction(&self) -> f64 {
+        self.direction
+    }
+
+    /// Whether the zone is a line instead of an area
+    pub fn is_line(&self) -> bool {
+        self.line
+    }
+
+    /// Whether the position lies inside the zone
+    ///
+    /// Lines are treated as the half disc on the zone side of the line.
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        self.contains_position((latitude, longitude))
+    }
+
+    /// Returns where the straight path `from` → `to` crosses the line, in
+    /// either direction
+    ///
+    /// The path leaves the zone if [`ZoneGeometry::contains()`] is true for
+    /// `from`. Always returns `None` if the zone is not a line.
+    pub fn line_crossing(&self, from: &Coordinate, to: &Coordinate) -> Option<Coordinate> {
+        if !self.line || self.r1 <= 0.0 {
+            return None;
+        }
+
+        // Intersect in a local plane around the center, with the line along the x axis
+        let local = |position: (f64, f64)| {
+            let (distance, bearing) = MODEL.inverse(self.center, position);
+            let angle = angle_difference(self.direction + 90.0, bearing).to_radians();
+            (distance * angle.cos(), distance * angle.sin())
+        };
+
+        let (x1, y1) = local(from.position());
+        let (x2, y2) = local(to.position());
+        if y1 == y2 || y1.signum() == y2.signum() && y1 != 0.0 && y2 != 0.0 {
+            return None;
+        }
+
+        let x = x1 + (x2 - x1) * y1 / (y1 - y2);
+        if x.abs() > self.r1 {
+            return None;
+        }
+
+        let (lat, lon) = match x < 0.0 {
+            true => MODEL.direct(self.center, self.direction - 90.0, -x),
+            false => MODEL.direct(self.center, self.direction + 90.0, x),
+        };
+        Some(Coordinate::new_unchecked(lat, lon))
+    }
+
+    /// Outline of the zone for display
+    ///
+    /// Returns a closed, counterclockwise ring (the first and last points are
+    /// equal) as KML and GeoJSON expect it, or the two end points for lines.
+    pub fn polygon(&self) -> Vec<Coordinate> {
+        let to_coordinate = |(lat, lon): (f64, f64)| Coordinate::new_unchecked(lat, lon);
+
+        if self.line {
+            let left = MODEL.direct(self.center, self.direction - 90.0, self.r1);
+            let right = MODEL.direct(self.center, self.direction + 90.0, self.r1);
+            return vec![to_coordinate(left), to_coordinate(right)];
+        }
+
+        // The zone is star-shaped around the center, so walk around it and
+        // use the radius of the zone in every direction
+        let mut offsets = vec![-self.a1, self.a1, -self.a2, self.a2];
+        let steps = (360.0 / POLYGON_STEP) as usize;
+        offsets.extend((0..steps).map(|i| -180.0 + i as f64 * POLYGON_STEP));
+        offsets.retain(|offset| (-180.0..180.0).contains(offset));
+        offsets.sort_by(f64::total_cmp);
+        offsets.dedup();
+
+        let mut points: Vec<(f64, f64)> = Vec::new();
+        let mut push = |position: (f64, f64)| {
+            if points.last() != Some(&position) {
+                points.push(position);
+            }
+        };
+
+        for offset in offsets {
+            let bearing = self.direction + offset;
+            let before = self.radius_at(offset, false);
+            let after = self.radius_at(offset, true);
+            for radius in [before, after] {
+                match radius {
+                    0.0 => push(self.center),
+                    radius => push(MODEL.direct(self.center, bearing, radius)),
+                }
+            }
+        }
+
+        if let Some(&first) = points.first() {
+            points.push(first);
+        }
+
+        // Bearings grow clockwise
+        points.reverse();
+        points.into_iter().map(to_coordinate).collect()
+    }
+
+    /// Radius of the zone at `offset` degrees from its direction, approached
+    /// from below (`after = false`) or above (`after = true`)
+    fn radius_at(&self, offset: f64, after: bool) -> f64 {
+        let within = |half_angle: f64| match after {
+            true => offset >= -half_angle && offset < half_angle,
+            false => offset > -half_angle && offset <= half_angle,
+        };
+
+        let mut radius: f64 = 0.0;
+        if self.a1 >= 180.0 || within(self.a1) {
+            radius = radius.max(self.r1);
+        }
+        if self.a2 >= 180.0 || within(self.a2) {
+            radius = radius.max(self.r2);
+        }
+        radius
+    }
+
+    /// Geometry for every task point, oriented by the neighbouring points
+    pub(crate) fn for_task(points: &[TaskPoint<'_>]) -> Vec<ZoneGeometry> {
+        let positions = points
+            .iter()
+            .map(|point| point.waypoint.position())
+            .collect::<Vec<_>>();
+
+        points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                let previous = i.checked_sub(1).map(|i| positions[i]);
+                let next = positions.get(i + 1).copied();
+                let start = positions.first().copied().filter(|_| i > 0);
+                match point.observation_zone {
+                    Some(zone) => Self::new(zone, positions[i], previous, next, start),
+                    None => Self::point(positions[i]),
+                }
+            })
+            .collect()
+    }
+
+    /// Zone without any extent, i.e. the task point itself
+    pub(crate) fn point(center: (f64, f64)) -> Self {
+        Self {
+            center,
+            direction: 0.0,
+            r1: 0.0,
+            a1: 180.0,
+            r2: 0.0,
+            a2: 0.0,
+            line: false,
+        }
+    }
+
+    /// Places `zone` at `center` and orients it according to its [`ObsZoneStyle`]
+    ///
+    /// Missing angles default to 180°, i.e. full cylinders.
+    pub(crate) fn new(
+        zone: &ObservationZone,
+        center: (f64, f64),
+        previous: Option<(f64, f64)>,
+        next: Option<(f64, f64)>,
+        start: Option<(f64, f64)>,
+    ) -> Self {
+        let bearing_to = |other: (f64, f64)| MODEL.inverse(center, other).1;
+        let away_from = |other: (f64, f64)| (bearing_to(other) + 180.0).rem_euclid(360.0);
+
+        let symmetrical = || match (previous, next) {
+            (Some(previous), Some(next)) => {
+                let inbound = bearing_to(previous);
+                let outbound = bearing_to(next);
+                let bisector = inbound + angle_difference(inbound, outbound) / 2.0;
+                // The zone lies on the outside of the turn
+                Some((bisector + 180.0).rem_euclid(360.0))
+            }
+            (Some(other), None) | (None, Some(other)) => Some(away_from(other)),
+            (None, None) => None,
+        };
+
+        let direction = match zone.style {
+            ObsZoneStyle::Fixed => zone.a12,
+            ObsZoneStyle::Symmetrical => symmetrical(),
+            ObsZoneStyle::ToNextPoint => next.map(away_from),
+            ObsZoneStyle::ToPreviousPoint => previous.map(away_from),
+            ObsZoneStyle::ToStartPoint => start.map(away_from),
+        };
+        let direction = direction.or_else(symmetrical).unwrap_or(0.0);
+
+        let r1 = zone.r1.as_ref().map_or(0.0, |r| r.to_meters().max(0.0));
+        let r2 = zone.r2.as_ref().map_or(0.0, |r| r.to_meters().max(0.0));
+
+        Self {
+            center,
+            direction: direction.rem_euclid(360.0),
+            r1,
+            a1: zone.a1.unwrap_or(180.0).clamp(0.0, 180.0),
+            r2,
+            a2: zone.a2.unwrap_or(180.0).clamp(0.0, 180.0),
+            line: zone.line.unwrap_or(false),
+        }
+    }
+
+    pub(crate) fn position(&self) -> (f64, f64) {
+        self.center
+    }
+
+    /// Whether the zone has no extent
+    pub(crate) fn is_point(&self) -> bool {
+        self.r1 == 0.0 && self.r2 == 0.0
+    }
+
+    /// Whether `position` lies inside the zone
+    ///
+    /// Lines are treated as the half disc behind the line.
+    pub(crate) fn contains_position(&self, position: (f64, f64)) -> bool {
+        let (distance, bearing) = MODEL.inverse(self.center, position);
+        if distance == 0.0 {
+            return true;
+        }
+
+        let offset = angle_difference(self.direction, bearing).abs();
+
+        if self.line {
+            return distance <= self.r1 && offset <= 90.0;
+        }
+
+        (distance <= self.r1 && offset <= self.a1) || (distance <= self.r2 && offset <= self.a2)
+    }
+
+    /// Curves making up the outline of the zone
+    ///
+    /// For sectors with an inner radius, the outlines of both parts are
+    /// returned, so some of the curves may lie inside the zone.
+    pub(crate) fn outline(&self) -> Vec<Curve> {
+        if self.line {
+            let bearing = (self.direction + 90.0).rem_euclid(360.0);
+            let (from, to) = (-self.r1, self.r1);
+            return vec![Curve::Radial { bearing, from, to }];
+        }
+
+        let mut curves = sector_outline(self.direction, self.r1, self.a1);
+        if self.r2 > 0.0 {
+            curves.extend(sector_outline(self.direction, self.r2, self.a2));
+        }
+        curves
+    }
+
+    /// Position on `curve` at parameter `t` (0 to 1)
+    pub(crate) fn position_on(&self, curve: &Curve, t: f64) -> (f64, f64) {
+        let (bearing, distance) = match *curve {
+            Curve::Arc {
+                radius,
+                start,
+                sweep,
+            } => (start + sweep * t, radius),
+            Curve::Radial { bearing, from, to } => (bearing, from + (to - from) * t),
+        };
+
+        if distance < 0.0 {
+            MODEL.direct(self.center, bearing + 180.0, -distance)
+        } else {
+            MODEL.direct(self.center, bearing, distance)
+        }
+    }
+}
+
+impl Curve {
+    /// Whether the curve ends where it starts
+    pub(crate) fn is_closed(&self) -> bool {
+        matches!(self, Curve::Arc { sweep, .. } if *sweep >= 360.0)
+    }
+}
+
+fn sector_outline(direction: f64, radius: f64, half_angle: f64) -> Vec<Curve> {
+    if radius <= 0.0 || half_angle <= 0.0 {
+        return Vec::new();
+    }
+
+    if half_angle >= 180.0 {
+        return vec![Curve::Arc {
+            radius,
+            start: 0.0,
+            sweep: 360.0,
+        }];
+    }
+
+    let start = direction - half_angle;
+    let end = direction + half_angle;
+    vec![
+        Curve::Radial {
+            bearing: start,
+            from: 0.0,
+            to: radius,
+        },
+        Curve::Arc {
+            radius,
+            start,
+            sweep: 2.0 * half_angle,
+        },
+        Curve::Radial {
+            bearing: end,
+            from: radius,
+            to: 0.0,
+        },
+    ]
+}
+
+/// Signed difference `to - from` between two bearings, in the range -180 to 180
+pub(crate) fn angle_difference(from: f64, to: f64) -> f64 {
+    let difference = (to - from).rem_euclid(360.0);
+    if difference > 180.0 {
+        difference - 360.0
+    } else {
+        difference
+    }
+}
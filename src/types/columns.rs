@@ -0,0 +1,113 @@
+/// Waypoint column of a CUP file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column {
+    Name,
+    Code,
+    Country,
+    Lat,
+    Lon,
+    Elev,
+    Style,
+    RwDir,
+    RwLen,
+    RwWidth,
+    Freq,
+    Desc,
+    Userdata,
+    Pics,
+}
+
+impl Column {
+    /// All columns in the canonical order of the specification
+    pub const ALL: [Column; 14] = [
+        Column::Name,
+        Column::Code,
+        Column::Country,
+        Column::Lat,
+        Column::Lon,
+        Column::Elev,
+        Column::Style,
+        Column::RwDir,
+        Column::RwLen,
+        Column::RwWidth,
+        Column::Freq,
+        Column::Desc,
+        Column::Userdata,
+        Column::Pics,
+    ];
+
+    /// Key name used in the header line
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::Name => "name",
+            Column::Code => "code",
+            Column::Country => "country",
+            Column::Lat => "lat",
+            Column::Lon => "lon",
+            Column::Elev => "elev",
+            Column::Style => "style",
+            Column::RwDir => "rwdir",
+            Column::RwLen => "rwlen",
+            Column::RwWidth => "rwwidth",
+            Column::Freq => "freq",
+            Column::Desc => "desc",
+            Column::Userdata => "userdata",
+            Column::Pics => "pics",
+        }
+    }
+
+    /// Parses a header key name (case-insensitive)
+    pub fn from_header(s: &str) -> Option<Self> {
+        Column::ALL
+            .into_iter()
+            .find(|column| column.header().eq_ignore_ascii_case(s))
+    }
+
+    /// Whether the column must be present in every CUP file
+    pub fn is_required(&self) -> bool {
+        matches!(
+            self,
+            Column::Name
+                | Column::Code
+                | Column::Country
+                | Column::Lat
+                | Column::Lon
+                | Column::Elev
+                | Column::Style
+        )
+    }
+}
+
+/// Order of the waypoint columns in a CUP file
+///
+/// The default layout contains all columns in the canonical order of the
+/// specification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnLayout(Vec<Column>);
+
+impl ColumnLayout {
+    /// Creates a layout from the given columns, ignoring duplicates
+    pub fn new(columns: impl IntoIterator<Item = Column>) -> Self {
+        let mut result = Vec::new();
+        for column in columns {
+            if !result.contains(&column) {
+                result.push(column);
+            }
+        }
+        Self(result)
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.0
+    }
+
+    pub fn contains(&self, column: Column) -> bool {
+        self.0.contains(&column)
+    }
+}
+
+impl Default for ColumnLayout {
+    fn default() -> Self {
+        Self(Column::ALL.to_vec())
+    }
+}
@@ -0,0 +1,269 @@
+use crate::FromStr;
+use crate::parser::basics::{parse_latitude, parse_longitude};
+use std::fmt::{Display, Formatter};
+
+/// Geographic position in decimal degrees (WGS-1984)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinate {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl Coordinate {
+    /// Creates a coordinate, checking that latitude is between -90 and 90
+    /// and longitude between -180 and 180 degrees
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self, String> {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(format!(
+                "Latitude out of range: '{latitude}' (must be between -90 and 90)",
+            ));
+        }
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(format!(
+                "Longitude out of range: '{longitude}' (must be between -180 and 180)",
+            ));
+        }
+        Ok(Self {
+            latitude,
+            longitude,
+        })
+    }
+
+    /// Creates a coordinate from values that are known to be in range
+    pub(crate) fn new_unchecked(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+
+    pub(crate) fn position(&self) -> (f64, f64) {
+        (self.latitude, self.longitude)
+    }
+
+    /// Latitude in decimal degrees (positive = north)
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /// Longitude in decimal degrees (positive = east)
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Returns a [`Display`] implementation using the given notation
+    pub fn format(&self, format: CoordinateFormat) -> CoordinateDisplay {
+        CoordinateDisplay {
+            coordinate: *self,
+            format,
+        }
+    }
+}
+
+/// Coordinate notation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CoordinateFormat {
+    /// Decimal degrees, e.g. `51.79682°N 004.08339°W`
+    DecimalDegrees,
+    /// Degrees and decimal minutes, e.g. `51°47.809'N 004°05.003'W`
+    #[default]
+    DegreesDecimalMinutes,
+    /// Degrees, minutes and seconds, e.g. `51°47'48.5"N 004°05'00.2"W`
+    DegreesMinutesSeconds,
+    /// SeeYou CUP notation, e.g. `5147.809N 00405.003W`
+    SeeYou,
+}
+
+/// Helper returned by [`Coordinate::format()`]
+#[derive(Debug, Clone, Copy)]
+pub struct CoordinateDisplay {
+    coordinate: Coordinate,
+    format: CoordinateFormat,
+}
+
+impl Display for CoordinateDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let lat = self.coordinate.latitude;
+        let lon = self.coordinate.longitude;
+        let lat_hemisphere = if lat >= 0.0 { 'N' } else { 'S' };
+        let lon_hemisphere = if lon >= 0.0 { 'E' } else { 'W' };
+        let (lat, lon) = (lat.abs(), lon.abs());
+
+        match self.format {
+            CoordinateFormat::DecimalDegrees => {
+                write!(f, "{lat:08.5}°{lat_hemisphere} {lon:09.5}°{lon_hemisphere}")
+            }
+            CoordinateFormat::DegreesDecimalMinutes => {
+                let (lat_deg, lat_min) = to_degrees_minutes(lat);
+                let (lon_deg, lon_min) = to_degrees_minutes(lon);
+                write!(
+                    f,
+                    "{lat_deg:02}°{lat_min:06.3}'{lat_hemisphere} {lon_deg:03}°{lon_min:06.3}'{lon_hemisphere}"
+                )
+            }
+            CoordinateFormat::DegreesMinutesSeconds => {
+                let (lat_deg, lat_min, lat_sec) = to_degrees_minutes_seconds(lat);
+                let (lon_deg, lon_min, lon_sec) = to_degrees_minutes_seconds(lon);
+                write!(
+                    f,
+                    "{lat_deg:02}°{lat_min:02}'{lat_sec:04.1}\"{lat_hemisphere} {lon_deg:03}°{lon_min:02}'{lon_sec:04.1}\"{lon_hemisphere}"
+                )
+            }
+            CoordinateFormat::SeeYou => {
+                let (lat_deg, lat_min) = to_degrees_minutes(lat);
+                let (lon_deg, lon_min) = to_degrees_minutes(lon);
+                write!(
+                    f,
+                    "{lat_deg:02}{lat_min:06.3}{lat_hemisphere} {lon_deg:03}{lon_min:06.3}{lon_hemisphere}"
+                )
+            }
+        }
+    }
+}
+
+/// Splits an absolute angle into whole degrees and minutes, rounded to
+/// thousandths of a minute
+fn to_degrees_minutes(value: f64) -> (u32, f64) {
+    let total = (value * 60_000.0).round() as u64;
+    let degrees = (total / 60_000) as u32;
+    let minutes = (total % 60_000) as f64 / 1000.0;
+    (degrees, minutes)
+}
+
+/// Splits an absolute angle into whole degrees, whole minutes and seconds,
+/// rounded to tenths of a second
+fn to_degrees_minutes_seconds(value: f64) -> (u32, u32, f64) {
+    let total = (value * 36_000.0).round() as u64;
+    let degrees = (total / 36_000) as u32;
+    let minutes = (total % 36_000 / 600) as u32;
+    let seconds = (total % 600) as f64 / 10.0;
+    (degrees, minutes, seconds)
+}
+
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.format(CoordinateFormat::default()).fmt(f)
+    }
+}
+
+impl FromStr for Coordinate {
+    type Err = String;
+
+    /// Parses any of the [`CoordinateFormat`] notations, as well as signed
+    /// decimal degrees like `51.79682, -4.08339`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (lat_str, lon_str) =
+            split_coordinate(s).ok_or_else(|| format!("Invalid coordinate: '{s}'"))?;
+
+        let latitude = parse_angle(lat_str, Axis::Latitude)?;
+        let longitude = parse_angle(lon_str, Axis::Longitude)?;
+        Coordinate::new(latitude, longitude)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Latitude,
+    Longitude,
+}
+
+impl Axis {
+    fn name(&self) -> &'static str {
+        match self {
+            Axis::Latitude => "latitude",
+            Axis::Longitude => "longitude",
+        }
+    }
+
+    /// Returns the sign for a hemisphere letter of this axis
+    fn hemisphere_sign(&self, c: char) -> Option<f64> {
+        match (self, c.to_ascii_uppercase()) {
+            (Axis::Latitude, 'N') | (Axis::Longitude, 'E') => Some(1.0),
+            (Axis::Latitude, 'S') | (Axis::Longitude, 'W') => Some(-1.0),
+            _ => None,
+        }
+    }
+}
+
+fn is_lat_hemisphere(c: char) -> bool {
+    matches!(c, 'N' | 'S' | 'n' | 's')
+}
+
+fn is_lon_hemisphere(c: char) -> bool {
+    matches!(c, 'E' | 'W' | 'e' | 'w')
+}
+
+/// Splits a coordinate string into its latitude and longitude parts
+fn split_coordinate(s: &str) -> Option<(&str, &str)> {
+    if let Some(lat_idx) = s.find(is_lat_hemisphere) {
+        let (lat, lon) = if lat_idx == 0 {
+            // Prefix notation: `N51°47.809' W004°05.003'`
+            s.split_at(s.find(is_lon_hemisphere)?)
+        } else {
+            // Suffix notation: `51°47.809'N 004°05.003'W`
+            s.split_at(lat_idx + 1)
+        };
+        return Some((lat, lon));
+    }
+
+    // Signed decimal degrees: `51.79682, -4.08339` or `51.79682 -4.08339`
+    if let Some((lat, lon)) = s.split_once(',') {
+        return Some((lat, lon));
+    }
+    let mut parts = s.split_whitespace();
+    let lat = parts.next()?;
+    let lon = parts.next()?;
+    match parts.next() {
+        Some(_) => None,
+        None => Some((lat, lon)),
+    }
+}
+
+fn parse_angle(s: &str, axis: Axis) -> Result<f64, String> {
+    let s = s.trim_matches(|c: char| c.is_whitespace() || c == ',');
+
+    let seeyou = match axis {
+        Axis::Latitude => parse_latitude(s),
+        Axis::Longitude => parse_longitude(s),
+    };
+    if let Ok(value) = seeyou {
+        return Ok(value);
+    }
+
+    let invalid = || format!("Invalid {}: '{s}'", axis.name());
+
+    let mut body = s;
+    let mut sign = 1.0;
+    if let Some(c) = body.chars().next().filter(char::is_ascii_alphabetic) {
+        sign = axis.hemisphere_sign(c).ok_or_else(invalid)?;
+        body = &body[1..];
+    } else if let Some(c) = body.chars().last().filter(char::is_ascii_alphabetic) {
+        sign = axis.hemisphere_sign(c).ok_or_else(invalid)?;
+        body = &body[..body.len() - 1];
+    } else if let Some(rest) = body.strip_prefix('-') {
+        sign = -1.0;
+        body = rest;
+    }
+
+    let numbers = body
+        .split(|c: char| !c.is_ascii_digit() && c != '.')
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<f64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if body.contains(|c: char| c.is_alphabetic() || c == '-') {
+        return Err(invalid());
+    }
+
+    let value = match numbers[..] {
+        [degrees] => degrees,
+        [degrees, minutes] if minutes < 60.0 => degrees + minutes / 60.0,
+        [degrees, minutes, seconds] if minutes < 60.0 && seconds < 60.0 => {
+            degrees + minutes / 60.0 + seconds / 3600.0
+        }
+        _ => return Err(invalid()),
+    };
+
+    Ok(sign * value)
+}
@@ -1,7 +1,11 @@
+mod columns;
+mod coordinate;
 mod dimensions;
 mod task;
 mod waypoint;
 
+pub use columns::*;
+pub use coordinate::*;
 pub use dimensions::*;
 pub use task::*;
 pub use waypoint::*;
@@ -1,5 +1,6 @@
 use crate::types::waypoint::Waypoint;
 use crate::{Distance, Elevation};
+use std::time::Duration;
 
 /// Task definition from a CUP file
 #[derive(Debug, Clone, PartialEq)]
@@ -66,6 +67,34 @@ pub struct ObservationZone {
     pub line: Option<bool>,
 }
 
+impl TaskOptions {
+    /// [`TaskOptions::task_time`] as a duration
+    ///
+    /// Accepts `HH:MM:SS` and `HH:MM`. Returns `None` if the task time is
+    /// missing, invalid or too long to be represented.
+    pub fn task_duration(&self) -> Option<Duration> {
+        let task_time = self.task_time.as_deref()?.trim();
+        let parts = task_time
+            .split(':')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+
+        let (hours, minutes, seconds) = match parts[..] {
+            [hours, minutes] => (hours, minutes, 0),
+            [hours, minutes, seconds] => (hours, minutes, seconds),
+            _ => return None,
+        };
+        if minutes >= 60 || seconds >= 60 {
+            return None;
+        }
+
+        let seconds = hours
+            .checked_mul(3600)?
+            .checked_add(minutes * 60 + seconds)?;
+        Some(Duration::from_secs(seconds))
+    }
+}
+
 /// Observation zone direction style
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ObsZoneStyle {
@@ -1,4 +1,4 @@
-use crate::{Elevation, RunwayDimension};
+use crate::{Coordinate, Elevation, RunwayDimension};
 
 /// Waypoint information from a CUP file
 #[derive(Debug, Clone, PartialEq)]
@@ -33,6 +33,19 @@ pub struct Waypoint {
     pub pictures: Vec<String>,
 }
 
+impl Waypoint {
+    /// Returns the position of the waypoint, or an error if the latitude or
+    /// longitude is out of range
+    pub fn coordinate(&self) -> Result<Coordinate, String> {
+        Coordinate::new(self.latitude, self.longitude)
+    }
+
+    pub fn set_coordinate(&mut self, coordinate: Coordinate) {
+        self.latitude = coordinate.latitude();
+        self.longitude = coordinate.longitude();
+    }
+}
+
 /// Waypoint style/type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WaypointStyle {
@@ -59,3 +72,72 @@ pub enum WaypointStyle {
     PgTakeOff = 20,
     PgLandingZone = 21,
 }
+
+impl WaypointStyle {
+    /// All styles in the order of their numeric value
+    pub const ALL: [WaypointStyle; 22] = [
+        WaypointStyle::Unknown,
+        WaypointStyle::Waypoint,
+        WaypointStyle::GrassAirfield,
+        WaypointStyle::Outlanding,
+        WaypointStyle::GlidingAirfield,
+        WaypointStyle::SolidAirfield,
+        WaypointStyle::MountainPass,
+        WaypointStyle::MountainTop,
+        WaypointStyle::TransmitterMast,
+        WaypointStyle::Vor,
+        WaypointStyle::Ndb,
+        WaypointStyle::CoolingTower,
+        WaypointStyle::Dam,
+        WaypointStyle::Tunnel,
+        WaypointStyle::Bridge,
+        WaypointStyle::PowerPlant,
+        WaypointStyle::Castle,
+        WaypointStyle::Intersection,
+        WaypointStyle::Marker,
+        WaypointStyle::ControlPoint,
+        WaypointStyle::PgTakeOff,
+        WaypointStyle::PgLandingZone,
+    ];
+
+    /// Name of the style as given in the specification
+    pub fn description(&self) -> &'static str {
+        match self {
+            WaypointStyle::Unknown => "Unknown",
+            WaypointStyle::Waypoint => "Waypoint",
+            WaypointStyle::GrassAirfield => "Airfield with grass surface runway",
+            WaypointStyle::Outlanding => "Outlanding",
+            WaypointStyle::GlidingAirfield => "Gliding airfield",
+            WaypointStyle::SolidAirfield => "Airfield with solid surface runway",
+            WaypointStyle::MountainPass => "Mountain Pass",
+            WaypointStyle::MountainTop => "Mountain Top",
+            WaypointStyle::TransmitterMast => "Transmitter Mast",
+            WaypointStyle::Vor => "VOR",
+            WaypointStyle::Ndb => "NDB",
+            WaypointStyle::CoolingTower => "Cooling Tower",
+            WaypointStyle::Dam => "Dam",
+            WaypointStyle::Tunnel => "Tunnel",
+            WaypointStyle::Bridge => "Bridge",
+            WaypointStyle::PowerPlant => "Power Plant",
+            WaypointStyle::Castle => "Castle",
+            WaypointStyle::Intersection => "Intersection",
+            WaypointStyle::Marker => "Marker",
+            WaypointStyle::ControlPoint => "Control/Reporting Point",
+            WaypointStyle::PgTakeOff => "PG Take Off",
+            WaypointStyle::PgLandingZone => "PG Landing Zone",
+        }
+    }
+
+    /// Whether a glider can land at the waypoint (airfields, outlanding
+    /// fields and paraglider landing zones)
+    pub fn is_landable(&self) -> bool {
+        matches!(
+            self,
+            WaypointStyle::GrassAirfield
+                | WaypointStyle::Outlanding
+                | WaypointStyle::GlidingAirfield
+                | WaypointStyle::SolidAirfield
+                | WaypointStyle::PgLandingZone
+        )
+    }
+}
@@ -0,0 +1,139 @@
+use crate::error::Warning;
+use crate::transliterate::transliterate;
+use crate::{CupFile, Encoding, EncodingFallback, Task, Waypoint};
+use encoding_rs::WINDOWS_1252;
+
+/// Returns a copy of `cup_file` in which all characters that can't be
+/// represented in `encoding` have been replaced according to `fallback`
+pub fn apply_fallback(
+    cup_file: &CupFile,
+    encoding: Encoding,
+    fallback: EncodingFallback,
+    warnings: &mut Vec<Warning>,
+) -> CupFile {
+    let mut cup_file = cup_file.clone();
+    let converter = Converter { encoding, fallback };
+
+    for waypoint in &mut cup_file.waypoints {
+        let original_name = waypoint.name.clone();
+        if converter.convert_waypoint(waypoint) {
+            warnings.push(converter.warning(format!("waypoint '{original_name}'")));
+        }
+    }
+
+    for (task_idx, task) in cup_file.tasks.iter_mut().enumerate() {
+        let task_label = match &task.description {
+            Some(description) => format!("task '{description}'"),
+            None => format!("task #{}", task_idx + 1),
+        };
+
+        if converter.convert_task(task) {
+            warnings.push(converter.warning(task_label.clone()));
+        }
+
+        for (_, waypoint) in &mut task.points {
+            let original_name = waypoint.name.clone();
+            if converter.convert_waypoint(waypoint) {
+                let subject = format!("inline waypoint '{original_name}' of {task_label}");
+                warnings.push(converter.warning(subject));
+            }
+        }
+    }
+
+    cup_file
+}
+
+struct Converter {
+    encoding: Encoding,
+    fallback: EncodingFallback,
+}
+
+impl Converter {
+    fn warning(&self, subject: String) -> Warning {
+        let action = match self.fallback {
+            EncodingFallback::Transliterate => "Transliterated",
+            _ => "Replaced",
+        };
+        let encoding = self.encoding;
+        let message = format!("{action} characters unsupported by {encoding:?} in {subject}");
+        Warning::encoding_fallback(message)
+    }
+
+    fn convert_waypoint(&self, waypoint: &mut Waypoint) -> bool {
+        let mut changed = false;
+        changed |= self.convert(&mut waypoint.name);
+        changed |= self.convert(&mut waypoint.code);
+        changed |= self.convert(&mut waypoint.country);
+        changed |= self.convert(&mut waypoint.frequency);
+        changed |= self.convert(&mut waypoint.description);
+        changed |= self.convert(&mut waypoint.userdata);
+        for picture in &mut waypoint.pictures {
+            changed |= self.convert(picture);
+        }
+        changed
+    }
+
+    fn convert_task(&self, task: &mut Task) -> bool {
+        let mut changed = false;
+        if let Some(description) = &mut task.description {
+            changed |= self.convert(description);
+        }
+        for name in &mut task.waypoint_names {
+            changed |= self.convert(name);
+        }
+        for name in &mut task.multiple_starts {
+            changed |= self.convert(name);
+        }
+        if let Some(options) = &mut task.options {
+            if let Some(no_start) = &mut options.no_start {
+                changed |= self.convert(no_start);
+            }
+            if let Some(task_time) = &mut options.task_time {
+                changed |= self.convert(task_time);
+            }
+        }
+        changed
+    }
+
+    /// Converts `s` in place and returns whether anything had to be replaced
+    fn convert(&self, s: &mut String) -> bool {
+        if s.chars().all(|c| self.is_representable(c)) {
+            return false;
+        }
+
+        let mut result = String::with_capacity(s.len());
+        for c in s.chars() {
+            if self.is_representable(c) {
+                result.push(c);
+            } else if let Some(replacement) = self.transliteration(c) {
+                result.push_str(replacement);
+            } else {
+                result.push('?');
+            }
+        }
+
+        *s = result;
+        true
+    }
+
+    fn transliteration(&self, c: char) -> Option<&'static str> {
+        match self.fallback {
+            EncodingFallback::Transliterate => transliterate(c),
+            _ => None,
+        }
+    }
+
+    fn is_representable(&self, c: char) -> bool {
+        match self.encoding {
+            Encoding::Utf8 => true,
+            Encoding::Windows1252 => {
+                if c.is_ascii() {
+                    return true;
+                }
+                let mut buf = [0; 4];
+                let (_, _, had_errors) = WINDOWS_1252.encode(c.encode_utf8(&mut buf));
+                !had_errors
+            }
+        }
+    }
+}
@@ -1,18 +1,34 @@
 mod basics;
+mod fallback;
 mod task;
 mod waypoint;
 
-use crate::CupFile;
-use crate::Encoding;
-use crate::error::Error;
+use crate::error::{Error, Warning};
+use crate::writer::fallback::apply_fallback;
 use crate::writer::task::format_task;
 use crate::writer::waypoint::write_waypoint;
+use crate::{Column, CupFile, Encoding, EncodingFallback, Waypoint};
 use csv::Writer;
 use encoding_rs::{Encoding as EncodingImpl, UTF_8, WINDOWS_1252};
+use std::borrow::Cow;
 use std::io::Write;
 
-pub fn write<W: Write>(cup_file: &CupFile, mut writer: W, encoding: Encoding) -> Result<(), Error> {
-    let content = format_cup_file(cup_file)?;
+pub(crate) use crate::writer::waypoint::format_field;
+
+pub fn write<W: Write>(
+    cup_file: &CupFile,
+    mut writer: W,
+    encoding: Encoding,
+    fallback: EncodingFallback,
+) -> Result<Vec<Warning>, Error> {
+    let mut warnings = Vec::new();
+
+    let cup_file = match fallback {
+        EncodingFallback::Error => Cow::Borrowed(cup_file),
+        _ => Cow::Owned(apply_fallback(cup_file, encoding, fallback, &mut warnings)),
+    };
+
+    let content = format_cup_file(&cup_file)?;
 
     let encoding_impl: &'static EncodingImpl = match encoding {
         Encoding::Utf8 => UTF_8,
@@ -28,20 +44,18 @@ pub fn write<W: Write>(cup_file: &CupFile, mut writer: W, encoding: Encoding) ->
     }
 
     writer.write_all(&encoded_bytes)?;
-    Ok(())
+    Ok(warnings)
 }
 
 fn format_cup_file(cup_file: &CupFile) -> Result<String, Error> {
     let mut output = Vec::new();
     let mut csv_writer = Writer::from_writer(&mut output);
 
-    csv_writer.write_record([
-        "name", "code", "country", "lat", "lon", "elev", "style", "rwdir", "rwlen", "rwwidth",
-        "freq", "desc", "userdata", "pics",
-    ])?;
+    let columns = output_columns(cup_file);
+    csv_writer.write_record(columns.iter().map(Column::header))?;
 
     for waypoint in &cup_file.waypoints {
-        write_waypoint(&mut csv_writer, waypoint)?;
+        write_waypoint(&mut csv_writer, waypoint, &columns)?;
     }
 
     csv_writer.flush()?;
@@ -53,10 +67,33 @@ fn format_cup_file(cup_file: &CupFile) -> Result<String, Error> {
         result.push_str("-----Related Tasks-----\n");
 
         for task in &cup_file.tasks {
-            result.push_str(&format_task(task)?);
+            result.push_str(&format_task(task, &columns)?);
             result.push('\n');
         }
     }
 
     Ok(result)
 }
+
+/// Returns the columns of the file's layout, followed by any missing column
+/// that is either required or holds data for at least one waypoint
+fn output_columns(cup_file: &CupFile) -> Vec<Column> {
+    let mut columns = cup_file.columns.columns().to_vec();
+
+    let inline_waypoints = cup_file.tasks.iter().flat_map(|task| &task.points);
+    let inline_waypoints = inline_waypoints.map(|(_, waypoint)| waypoint);
+    let all_waypoints = cup_file.waypoints.iter().chain(inline_waypoints);
+
+    for column in Column::ALL {
+        if columns.contains(&column) {
+            continue;
+        }
+
+        let has_data = |waypoint: &Waypoint| !format_field(waypoint, column).is_empty();
+        if column.is_required() || all_waypoints.clone().any(has_data) {
+            columns.push(column);
+        }
+    }
+
+    columns
+}
@@ -1,8 +1,8 @@
-use crate::writer::basics;
-use crate::{Error, ObservationZone, Task, TaskOptions, Waypoint};
+use crate::writer::waypoint::format_field;
+use crate::{Column, Error, ObservationZone, Task, TaskOptions, Waypoint};
 use csv::Writer;
 
-pub fn format_task(task: &Task) -> Result<String, Error> {
+pub fn format_task(task: &Task, columns: &[Column]) -> Result<String, Error> {
     let mut result = String::new();
 
     // Write the task line with waypoint names
@@ -23,6 +23,11 @@ pub fn format_task(task: &Task) -> Result<String, Error> {
 
         let task_line = String::from_utf8(output).map_err(|e| Error::Encoding(e.to_string()))?;
         result.push_str(task_line.trim_end());
+
+        // An empty landing is quoted, since the parser ignores a trailing comma
+        if result.ends_with(',') {
+            result.push_str("\"\"");
+        }
     }
 
     // Write task options if present
@@ -40,7 +45,11 @@ pub fn format_task(task: &Task) -> Result<String, Error> {
     // Write inline waypoints as separate Point= lines
     for (idx, waypoint) in &task.points {
         result.push('\n');
-        result.push_str(&format_inline_waypoint_line(*idx as usize, waypoint)?);
+        result.push_str(&format_inline_waypoint_line(
+            *idx as usize,
+            waypoint,
+            columns,
+        )?);
     }
 
     // Write multiple starts if present
@@ -129,46 +138,21 @@ fn format_multiple_starts(starts: &[String]) -> Result<String, Error> {
     Ok(format!("STARTS={}", quoted_starts.join(",")))
 }
 
-fn format_inline_waypoint_line(index: usize, waypoint: &Waypoint) -> Result<String, Error> {
+fn format_inline_waypoint_line(
+    index: usize,
+    waypoint: &Waypoint,
+    columns: &[Column],
+) -> Result<String, Error> {
     // Format: Point=1,"Point_3",PNT_3,,4627.136N,01412.856E,0.0m,1,,,,,,,
-    let pics = if waypoint.pictures.is_empty() {
-        String::new()
-    } else {
-        waypoint.pictures.join(";")
-    };
+    // The fields use the same column order as the waypoint section
+    let mut record = vec![format!("Point={}", index)];
+    record.extend(columns.iter().map(|&column| format_field(waypoint, column)));
 
     // Create a CSV writer to properly format the waypoint data
     let mut output = Vec::new();
     {
         let mut csv_writer = Writer::from_writer(&mut output);
-        csv_writer.write_record([
-            &format!("Point={}", index),
-            &waypoint.name,
-            &waypoint.code,
-            &waypoint.country,
-            &basics::format_latitude(waypoint.latitude),
-            &basics::format_longitude(waypoint.longitude),
-            &waypoint.elevation.to_string(),
-            &(waypoint.style as u8).to_string(),
-            &waypoint
-                .runway_direction
-                .map(|d| format!("{:03}", d))
-                .unwrap_or_default(),
-            &waypoint
-                .runway_length
-                .as_ref()
-                .map(ToString::to_string)
-                .unwrap_or_default(),
-            &waypoint
-                .runway_width
-                .as_ref()
-                .map(ToString::to_string)
-                .unwrap_or_default(),
-            &waypoint.frequency,
-            &waypoint.description,
-            &waypoint.userdata,
-            &pics,
-        ])?;
+        csv_writer.write_record(&record)?;
         csv_writer.flush()?;
     }
 
@@ -1,44 +1,42 @@
 use crate::writer::basics::{format_latitude, format_longitude};
-use crate::{Error, Waypoint};
+use crate::{Column, Error, Waypoint};
 use csv::Writer;
 
 pub fn write_waypoint<W: std::io::Write>(
     writer: &mut Writer<W>,
     waypoint: &Waypoint,
+    columns: &[Column],
 ) -> Result<(), Error> {
-    let pics = if waypoint.pictures.is_empty() {
-        String::new()
-    } else {
-        waypoint.pictures.join(";")
-    };
+    writer.write_record(columns.iter().map(|&column| format_field(waypoint, column)))?;
+    Ok(())
+}
 
-    writer.write_record([
-        &waypoint.name,
-        &waypoint.code,
-        &waypoint.country,
-        &format_latitude(waypoint.latitude),
-        &format_longitude(waypoint.longitude),
-        &waypoint.elevation.to_string(),
-        &(waypoint.style as u8).to_string(),
-        &waypoint
+pub fn format_field(waypoint: &Waypoint, column: Column) -> String {
+    match column {
+        Column::Name => waypoint.name.clone(),
+        Column::Code => waypoint.code.clone(),
+        Column::Country => waypoint.country.clone(),
+        Column::Lat => format_latitude(waypoint.latitude),
+        Column::Lon => format_longitude(waypoint.longitude),
+        Column::Elev => waypoint.elevation.to_string(),
+        Column::Style => (waypoint.style as u8).to_string(),
+        Column::RwDir => waypoint
             .runway_direction
             .map(|d| format!("{:03}", d))
             .unwrap_or_default(),
-        &waypoint
+        Column::RwLen => waypoint
             .runway_length
             .as_ref()
             .map(ToString::to_string)
             .unwrap_or_default(),
-        &waypoint
+        Column::RwWidth => waypoint
             .runway_width
             .as_ref()
             .map(ToString::to_string)
             .unwrap_or_default(),
-        &waypoint.frequency,
-        &waypoint.description,
-        &waypoint.userdata,
-        &pics,
-    ])?;
-
-    Ok(())
+        Column::Freq => waypoint.frequency.clone(),
+        Column::Desc => waypoint.description.clone(),
+        Column::Userdata => waypoint.userdata.clone(),
+        Column::Pics => waypoint.pictures.join(";"),
+    }
 }
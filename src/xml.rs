@@ -0,0 +1,72 @@
+use std::fmt::Write;
+
+/// Minimal writer for indented XML documents
+pub struct XmlWriter {
+    output: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    pub fn new() -> Self {
+        let output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        Self { output, depth: 0 }
+    }
+
+    /// Writes a start tag and indents the following elements
+    pub fn open(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.start_tag(name, attributes);
+        self.output.push_str(">\n");
+        self.depth += 1;
+    }
+
+    pub fn close(&mut self, name: &str) {
+        self.depth -= 1;
+        self.indent();
+        let _ = writeln!(self.output, "</{name}>");
+    }
+
+    /// Writes an element with text content
+    pub fn element(&mut self, name: &str, attributes: &[(&str, &str)], text: &str) {
+        self.start_tag(name, attributes);
+        let _ = writeln!(self.output, ">{}</{name}>", escape(text));
+    }
+
+    /// Writes an element without content
+    pub fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.start_tag(name, attributes);
+        self.output.push_str("/>\n");
+    }
+
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    fn start_tag(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.indent();
+        let _ = write!(self.output, "<{name}");
+        for (key, value) in attributes {
+            let _ = write!(self.output, " {key}=\"{}\"", escape(value));
+        }
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.output.push_str("  ");
+        }
+    }
+}
+
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,99 @@
+use claims::{assert_err, assert_none, assert_ok, assert_some};
+use insta::assert_snapshot;
+use seeyou_cup::{Coordinate, CupFile, TaskOptions};
+use std::time::Duration;
+
+const AAT: &str = r#"name,code,country,lat,lon,elev,style
+"Home","H",XX,0000.000N,00000.000E,0m,4
+"East","E",XX,0000.000N,00100.000E,0m,1
+"North","N",XX,0100.000N,00000.000E,0m,1
+-----Related Tasks-----
+"AAT","Home","Home","East","North","Home","Home"
+Options,TaskTime=03:00:00
+ObsZone=0,Style=2,R1=5000m,A1=180,Line=1
+ObsZone=1,Style=1,R1=20000m,A1=180
+ObsZone=2,Style=1,R1=20000m,A1=180
+ObsZone=3,Style=3,R1=1000m,A1=180
+"#;
+
+fn km(meters: f64) -> f64 {
+    (meters / 10.0).round() / 100.0
+}
+
+#[test]
+fn test_aat_distances() {
+    let (cup, _) = assert_ok!(CupFile::from_str(AAT));
+    let distances = assert_ok!(cup.tasks[0].aat_distances(&cup.waypoints));
+
+    assert_eq!(km(distances.minimum.total.to_meters()), 305.46);
+    assert_eq!(km(distances.nominal.total.to_meters()), 378.79);
+    assert_eq!(km(distances.maximum.total.to_meters()), 453.19);
+    assert_eq!(distances.task_time, Some(Duration::from_secs(3 * 3600)));
+
+    let minimum_speed = assert_some!(distances.minimum_speed());
+    let maximum_speed = assert_some!(distances.maximum_speed());
+    assert_eq!((minimum_speed * 10.0).round() / 10.0, 101.8);
+    assert_eq!((maximum_speed * 10.0).round() / 10.0, 151.1);
+
+    // Start and finish are measured at their centers
+    let start = distances.minimum.points[0];
+    assert_eq!((start.latitude(), start.longitude()), (0.0, 0.0));
+}
+
+#[test]
+fn test_aat_target_distance() {
+    let (cup, _) = assert_ok!(CupFile::from_str(AAT));
+    let task = &cup.tasks[0];
+
+    let targets = [
+        assert_ok!(Coordinate::new(0.0, 1.1)),
+        assert_ok!(Coordinate::new(1.0, 0.0)),
+    ];
+    let distance = assert_ok!(task.aat_target_distance(&cup.waypoints, &targets));
+    assert_eq!(distance.points[1], targets[0]);
+    assert_eq!(km(distance.total.to_meters()), 398.01);
+
+    let speed = assert_some!(distance.average_speed(Duration::from_secs(2 * 3600)));
+    assert_eq!((speed * 10.0).round() / 10.0, 199.0);
+}
+
+#[test]
+fn test_aat_invalid_targets() {
+    let (cup, _) = assert_ok!(CupFile::from_str(AAT));
+    let task = &cup.tasks[0];
+
+    let target = assert_ok!(Coordinate::new(0.0, 1.0));
+    let err = assert_err!(task.aat_target_distance(&cup.waypoints, &[target]));
+    assert_snapshot!(err, @"Invalid target: expected 2 targets, got 1");
+
+    let far_away = assert_ok!(Coordinate::new(0.0, 2.0));
+    let err = assert_err!(task.aat_target_distance(&cup.waypoints, &[far_away, target]));
+    assert_snapshot!(err, @"Invalid target: 00°00.000'N 002°00.000'E lies outside of the area of 'East'");
+}
+
+#[test]
+fn test_task_duration() {
+    let options = |task_time: &str| TaskOptions {
+        task_time: Some(task_time.to_string()),
+        ..TaskOptions::default()
+    };
+
+    assert_eq!(
+        options("01:45:12").task_duration(),
+        Some(Duration::from_secs(3600 + 45 * 60 + 12))
+    );
+    assert_eq!(
+        options("2:30").task_duration(),
+        Some(Duration::from_secs(2 * 3600 + 30 * 60))
+    );
+    assert_none!(options("01:75:00").task_duration());
+    assert_none!(options("soon").task_duration());
+    assert_none!(TaskOptions::default().task_duration());
+
+    // Too long for a duration in seconds
+    let overflow = AAT.replace("03:00:00", "99999999999999999:00:00");
+    let (cup, _) = assert_ok!(CupFile::from_str(&overflow));
+    let task = &cup.tasks[0];
+    assert_none!(task.options.as_ref().and_then(TaskOptions::task_duration));
+    assert_ok!(task.classify(&cup.waypoints));
+}
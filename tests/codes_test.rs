@@ -0,0 +1,180 @@
+mod common;
+
+use claims::{assert_err, assert_none, assert_ok, assert_some};
+use common::parse;
+use insta::assert_snapshot;
+use proptest::prelude::*;
+use seeyou_cup::{CodeGenerator, CodeOptions, CodeScope, CupFile};
+use std::collections::HashSet;
+
+const INPUT: &str = r#"name,code,country,lat,lon,elev,style
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5
+"Bled","LJBL",SI,4622.100N,01406.820E,501m,1
+"Radovljica","",SI,4620.800N,01410.500E,490m,1
+"Würselen","VERYLONGCODE",DE,5049.000N,00608.000E,180m,1
+-----Related Tasks-----
+"Local","Lesce","Lesce","Radovljica","Remote","Lesce","Lesce"
+Point=1,"Radovljica","",SI,4620.800N,01410.500E,490m,1
+Point=2,"Remote","",SI,4625.000N,01415.000E,800m,1
+"#;
+
+fn codes(cup: &CupFile) -> Vec<&str> {
+    cup.waypoints.iter().map(|w| w.code.as_str()).collect()
+}
+
+#[test]
+fn test_generate_code() {
+    let mut generator = CodeGenerator::new(8, true);
+
+    let codes = [
+        "002 Aachen Brand Abfahrt",
+        "003 Aachen Europaplatz",
+        "Lesce",
+        "Aalen Heidenheim",
+        "Helmstadter Berg",
+        "Würselen",
+        "Wuerselen",
+        "123456789",
+        "",
+    ]
+    .map(|name| assert_some!(generator.generate(name)));
+
+    assert_snapshot!(codes.join("\n"), @r"
+    002ACBRA
+    003ACHER
+    LESCE
+    ALNHDNHM
+    HLMSTBRG
+    WRSLN
+    WRSLN2
+    12345678
+    WP
+    ");
+}
+
+#[test]
+fn test_generate_code_clashes() {
+    let mut generator = CodeGenerator::new(4, false);
+    assert!(generator.reserve("BLED"));
+    assert!(!generator.reserve("bled"));
+
+    let codes = ["Bled", "Bled", "Bled"].map(|name| assert_some!(generator.generate(name)));
+    assert_eq!(codes, ["BLE2", "BLE3", "BLE4"]);
+}
+
+#[test]
+fn test_generate_code_counter() {
+    // The counter continues with letters, and then with more digits
+    let mut generator = CodeGenerator::new(2, false);
+    let codes = (0..37)
+        .map(|_| assert_some!(generator.generate("Bled")))
+        .collect::<Vec<_>>();
+    assert_eq!(codes[..3], ["BL", "B2", "B3"]);
+    assert_eq!(codes[8..10], ["B9", "BA"]);
+    assert_eq!(codes[33..], ["BZ", "10", "11", "12"]);
+}
+
+#[test]
+fn test_generate_code_exhausted() {
+    // `B`, then `2` to `9` and the other letters
+    let mut generator = CodeGenerator::new(1, false);
+    for _ in 0..34 {
+        let code = assert_some!(generator.generate("Bled"));
+        assert_eq!(code.len(), 1);
+    }
+    assert_none!(generator.generate("Bled"));
+
+    let mut cup = parse(INPUT);
+    for index in 0..40 {
+        let mut waypoint = cup.waypoints[0].clone();
+        waypoint.name = format!("Lesce {index}");
+        cup.waypoints.push(waypoint);
+    }
+    let original = cup.clone();
+    let options = CodeOptions {
+        max_length: 1,
+        scope: CodeScope::All,
+        ..CodeOptions::default()
+    };
+
+    let error = assert_err!(cup.generate_codes(&options));
+    assert_snapshot!(error, @"No unique code left for waypoint: 'Lesce 30'");
+    assert_eq!(cup, original);
+}
+
+#[test]
+fn test_fill_missing_codes() {
+    let mut cup = parse(INPUT);
+    let changes = assert_ok!(cup.generate_codes(&CodeOptions::default()));
+
+    assert_eq!(codes(&cup), ["LJBL", "LJBL", "RDVLJC", "VERYLONGCODE"]);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].name, "Radovljica");
+    assert_eq!(changes[0].old, "");
+
+    // Inline points follow the waypoint with the same name
+    let points = &cup.tasks[0].points;
+    assert_eq!(points[0].1.code, "RDVLJC");
+    assert_eq!(points[1].1.code, "REMOTE");
+}
+
+#[test]
+fn test_fix_invalid_codes() {
+    let mut cup = parse(INPUT);
+    let options = CodeOptions {
+        max_length: 6,
+        scope: CodeScope::Invalid,
+        ..CodeOptions::default()
+    };
+    assert_ok!(cup.generate_codes(&options));
+
+    assert_eq!(codes(&cup), ["LJBL", "BLED", "RDVLJC", "WRSLN"]);
+}
+
+#[test]
+fn test_recode_fixture() {
+    let (mut cup, _) = assert_ok!(CupFile::from_path("tests/fixtures/EC25.cup"));
+    let tasks = cup.tasks.clone();
+
+    let options = CodeOptions {
+        scope: CodeScope::All,
+        ..CodeOptions::default()
+    };
+    assert_ok!(cup.generate_codes(&options));
+
+    let unique = cup
+        .waypoints
+        .iter()
+        .map(|w| &w.code)
+        .collect::<HashSet<_>>();
+    assert_eq!(unique.len(), cup.waypoints.len());
+    assert!(cup.waypoints.iter().all(|w| w.code.len() <= 8));
+    assert_eq!(
+        codes(&cup)[..4],
+        ["001ACABK", "002ACBRA", "003ACHER", "004ACHMR"]
+    );
+    assert_eq!(cup.tasks, tasks);
+}
+
+proptest! {
+    #[test]
+    fn generated_codes_are_unique(
+        names in prop::collection::vec("[0-9]{0,3} ?[A-Za-zäöü ]{0,20}", 1..50),
+        max_length in 1..10usize,
+        numeric_prefix: bool,
+    ) {
+        let mut generator = CodeGenerator::new(max_length, numeric_prefix);
+        let mut seen = HashSet::new();
+        for name in &names {
+            // A single character only leaves 36 codes
+            let Some(code) = generator.generate(name) else {
+                prop_assert!(max_length == 1 && seen.len() >= 34);
+                continue;
+            };
+            prop_assert!(!code.is_empty());
+            prop_assert!(code.len() <= max_length, "{code} is too long");
+            prop_assert!(code.chars().all(|c| c.is_ascii_alphanumeric()));
+            prop_assert!(seen.insert(code));
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code)]
+
+use claims::assert_ok;
+use seeyou_cup::CupFile;
+
+/// Parses a CUP file, ignoring warnings
+pub fn parse(input: &str) -> CupFile {
+    let (cup, _) = assert_ok!(CupFile::from_str(input));
+    cup
+}
+
+/// Names of the waypoints in file order
+pub fn names(cup: &CupFile) -> Vec<&str> {
+    cup.waypoints.iter().map(|w| w.name.as_str()).collect()
+}
+
+/// Whether a ring of `(longitude, latitude)` positions is counterclockwise
+pub fn is_counterclockwise(ring: &[(f64, f64)]) -> bool {
+    let area = ring
+        .windows(2)
+        .map(|pair| pair[0].0 * pair[1].1 - pair[1].0 * pair[0].1)
+        .sum::<f64>();
+    area > 0.0
+}
@@ -0,0 +1,104 @@
+use claims::{assert_err, assert_ok};
+use insta::assert_snapshot;
+use seeyou_cup::{Coordinate, CoordinateFormat, CupFile};
+
+fn cross_hands() -> Coordinate {
+    assert_ok!(Coordinate::new(51.796817, -4.083383))
+}
+
+#[test]
+fn test_new_validates_range() {
+    assert_ok!(Coordinate::new(90.0, 180.0));
+    assert_ok!(Coordinate::new(-90.0, -180.0));
+    assert_snapshot!(assert_err!(Coordinate::new(90.1, 0.0)), @"Latitude out of range: '90.1' (must be between -90 and 90)");
+    assert_snapshot!(assert_err!(Coordinate::new(0.0, -180.5)), @"Longitude out of range: '-180.5' (must be between -180 and 180)");
+    assert_err!(Coordinate::new(f64::NAN, 0.0));
+}
+
+#[test]
+fn test_display_formats() {
+    let coordinate = cross_hands();
+    assert_snapshot!(coordinate, @"51°47.809'N 004°05.003'W");
+    assert_snapshot!(coordinate.format(CoordinateFormat::DecimalDegrees), @"51.79682°N 004.08338°W");
+    assert_snapshot!(coordinate.format(CoordinateFormat::DegreesDecimalMinutes), @"51°47.809'N 004°05.003'W");
+    assert_snapshot!(coordinate.format(CoordinateFormat::DegreesMinutesSeconds), @r#"51°47'48.5"N 004°05'00.2"W"#);
+    assert_snapshot!(coordinate.format(CoordinateFormat::SeeYou), @"5147.809N 00405.003W");
+}
+
+#[test]
+fn test_display_rounds_carry_into_degrees() {
+    let coordinate = assert_ok!(Coordinate::new(47.9999999, 7.9999999));
+    assert_snapshot!(coordinate, @"48°00.000'N 008°00.000'E");
+    assert_snapshot!(coordinate.format(CoordinateFormat::DegreesMinutesSeconds), @r#"48°00'00.0"N 008°00'00.0"E"#);
+}
+
+#[test]
+fn test_parse_notations() {
+    let cases = [
+        "5147.809N 00405.003W",
+        "51°47.809'N 004°05.003'W",
+        "51°47.809'N, 004°05.003'W",
+        "N51°47.809' W004°05.003'",
+        "51°47'48.5\"N 004°05'00.2\"W",
+        "51 47 48.5 N 4 05 00.2 W",
+        "51.79682°N 004.08338°W",
+        "51.79682, -4.08338",
+        "51.79682 -4.08338",
+    ];
+
+    let expected = cross_hands();
+    for input in cases {
+        let coordinate: Coordinate = assert_ok!(input.parse(), "{input}");
+        assert!(
+            (coordinate.latitude() - expected.latitude()).abs() < 0.00002,
+            "{input}"
+        );
+        assert!(
+            (coordinate.longitude() - expected.longitude()).abs() < 0.00002,
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn test_parse_roundtrip() {
+    let coordinate = assert_ok!(Coordinate::new(-33.8688, 151.2093));
+    for format in [
+        CoordinateFormat::DecimalDegrees,
+        CoordinateFormat::DegreesDecimalMinutes,
+        CoordinateFormat::DegreesMinutesSeconds,
+        CoordinateFormat::SeeYou,
+    ] {
+        let text = coordinate.format(format).to_string();
+        let parsed: Coordinate = assert_ok!(text.parse());
+        assert!((parsed.latitude() - coordinate.latitude()).abs() < 0.0001);
+        assert!((parsed.longitude() - coordinate.longitude()).abs() < 0.0001);
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_snapshot!(assert_err!("".parse::<Coordinate>()), @"Invalid coordinate: ''");
+    assert_snapshot!(assert_err!("51.5".parse::<Coordinate>()), @"Invalid coordinate: '51.5'");
+    assert_snapshot!(assert_err!("51°47.809'E 004°05.003'N".parse::<Coordinate>()), @"Invalid latitude: '51°47.809'E 004°05.003'N'");
+    assert_snapshot!(assert_err!("51°75'N 004°05'W".parse::<Coordinate>()), @"Invalid latitude: '51°75'N'");
+    assert_snapshot!(assert_err!("95.0, 4.0".parse::<Coordinate>()), @"Latitude out of range: '95' (must be between -90 and 90)");
+}
+
+#[test]
+fn test_waypoint_coordinate() {
+    let input = r#"name,code,country,lat,lon,elev,style
+"Cross Hands","CSS",UK,5147.809N,00405.003W,525ft,1
+"#;
+
+    let (mut cup, _) = assert_ok!(CupFile::from_str(input));
+    let coordinate = assert_ok!(cup.waypoints[0].coordinate());
+    assert_snapshot!(coordinate, @"51°47.809'N 004°05.003'W");
+
+    cup.waypoints[0].set_coordinate(assert_ok!("4621.379N 01410.467E".parse()));
+    assert!((cup.waypoints[0].latitude - 46.356317).abs() < 0.00001);
+    assert!((cup.waypoints[0].longitude - 14.174450).abs() < 0.00001);
+
+    cup.waypoints[0].latitude = 123.0;
+    assert_err!(cup.waypoints[0].coordinate());
+}
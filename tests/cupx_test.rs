@@ -0,0 +1,129 @@
+#![cfg(feature = "cupx")]
+
+use claims::{assert_err, assert_ok};
+use insta::assert_snapshot;
+use seeyou_cup::{CupFile, CupxFile, Error, MissingPicture};
+use std::io::{Cursor, Write};
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+const POINTS: &str = r#"name,code,country,lat,lon,elev,style,rwdir,rwlen,rwwidth,freq,desc,userdata,pics
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5,130,1140m,,123.500,,,"lesce.jpg;runway.jpg"
+"Bled","BLED",SI,4622.100N,01406.820E,501m,1,,,,,,,"bled.jpg"
+"Bohinj","BOH",SI,4617.000N,01352.000E,520m,1,,,,,,,
+"#;
+
+const TASKS: &str = r#"name,code,country,lat,lon,elev,style,rwdir,rwlen,rwwidth,freq,desc,userdata,pics
+-----Related Tasks-----
+"Day 1","Lesce","Lesce","Bohinj","Inline","Lesce","Lesce"
+Point=3,"Inline","INL",SI,4625.000N,01415.000E,800m,1,,,,,,,"inline.jpg"
+"#;
+
+fn archive(entries: &[(&str, &[u8])]) -> Cursor<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, bytes) in entries {
+        assert_ok!(zip.start_file(*name, SimpleFileOptions::default()));
+        assert_ok!(zip.write_all(bytes));
+    }
+    let mut cursor = assert_ok!(zip.finish());
+    cursor.set_position(0);
+    cursor
+}
+
+fn example() -> Cursor<Vec<u8>> {
+    archive(&[
+        ("POINTS.CUP", POINTS.as_bytes()),
+        ("tasks/day1.cup", TASKS.as_bytes()),
+        ("Pics/lesce.jpg", b"lesce"),
+        ("pics/bled.jpg", b"bled"),
+        ("pics/inline.jpg", b"inline"),
+        ("pics/unused.jpg", b"unused"),
+        ("readme.txt", b"ignored"),
+    ])
+}
+
+#[test]
+fn test_read_cupx() {
+    let (cupx, warnings) = assert_ok!(CupxFile::from_reader(example()));
+    assert!(warnings.is_empty());
+
+    let (points, _) = assert_ok!(CupFile::from_str(POINTS));
+    assert_eq!(cupx.cup, points);
+
+    let task_files = cupx.task_files.keys().collect::<Vec<_>>();
+    assert_eq!(task_files, ["tasks/day1.cup"]);
+    assert_eq!(cupx.task_files["tasks/day1.cup"].tasks.len(), 1);
+
+    let pictures = cupx.pictures.keys().collect::<Vec<_>>();
+    assert_eq!(
+        pictures,
+        ["bled.jpg", "inline.jpg", "lesce.jpg", "unused.jpg"]
+    );
+    assert_eq!(cupx.pictures["lesce.jpg"], b"lesce");
+}
+
+#[test]
+fn test_read_concatenated_cupx() {
+    // SeeYou joins an archive with the pictures and one with the waypoints
+    let pictures = archive(&[("pics/lesce.jpg", b"lesce"), ("pics/bled.jpg", b"bled")]);
+    let points = archive(&[("POINTS.CUP", POINTS.as_bytes())]);
+    let input = Cursor::new([pictures.into_inner(), points.into_inner()].concat());
+
+    let (cupx, warnings) = assert_ok!(CupxFile::from_reader(input));
+    assert!(warnings.is_empty());
+    assert_eq!(cupx.cup.waypoints.len(), 3);
+
+    let pictures = cupx.pictures.keys().collect::<Vec<_>>();
+    assert_eq!(pictures, ["bled.jpg", "lesce.jpg"]);
+    assert_eq!(cupx.pictures["bled.jpg"], b"bled");
+}
+
+#[test]
+fn test_write_cupx_roundtrip() {
+    let (cupx, _) = assert_ok!(CupxFile::from_reader(example()));
+
+    let mut output = Cursor::new(Vec::new());
+    assert_ok!(cupx.to_writer(&mut output));
+    output.set_position(0);
+
+    let (roundtrip, warnings) = assert_ok!(CupxFile::from_reader(output));
+    assert!(warnings.is_empty());
+    assert_eq!(roundtrip, cupx);
+}
+
+#[test]
+fn test_validate_pictures() {
+    let (mut cupx, _) = assert_ok!(CupxFile::from_reader(example()));
+
+    let report = cupx.validate_pictures();
+    assert!(!report.is_empty());
+    assert_eq!(
+        report.missing,
+        [MissingPicture {
+            waypoint: "Lesce".to_string(),
+            picture: "runway.jpg".to_string(),
+        }]
+    );
+    assert_eq!(report.orphans, ["unused.jpg"]);
+
+    cupx.pictures.remove("unused.jpg");
+    cupx.pictures
+        .insert("runway.jpg".to_string(), b"runway".to_vec());
+    assert!(cupx.validate_pictures().is_empty());
+}
+
+#[test]
+fn test_missing_points_file() {
+    let input = archive(&[("pics/lesce.jpg", b"lesce")]);
+
+    let error = assert_err!(CupxFile::from_reader(input));
+    assert_snapshot!(error, @"Parse error: Missing points.cup in CUPX archive");
+}
+
+#[test]
+fn test_invalid_archive() {
+    let input = Cursor::new(POINTS.as_bytes().to_vec());
+
+    let error = assert_err!(CupxFile::from_reader(input));
+    assert!(matches!(error, Error::Zip(_)));
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::parse;
+use insta::assert_snapshot;
+use seeyou_cup::{Column, TaskChange, TaskPart, WaypointChange};
+
+const OLD: &str = r#"name,code,country,lat,lon,elev,style
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5
+"Bled","BLED",SI,4622.100N,01406.820E,501m,1
+"Begunje","BEGU",SI,4623.000N,01412.000E,580m,1
+"Kranj","KRAN",SI,4614.000N,01421.000E,385m,1
+-----Related Tasks-----
+"Local","Lesce","Lesce","Bled","Kranj","Lesce","Lesce"
+"Triangle","Lesce","Lesce","Begunje","Kranj","Lesce","Lesce"
+Options,TaskTime=02:00:00
+"Removed","Lesce","Lesce","Kranj","Lesce","Lesce"
+"#;
+
+const NEW: &str = r#"name,code,country,lat,lon,elev,style
+"Kranj","KRAN",SI,4614.100N,01421.000E,385m,1
+"Lesce Bled","LJBL",SI,4621.380N,01410.470E,504m,5
+"Bled","BLED",SI,4622.100N,01406.820E,501m,3
+"Radovljica","RADO",SI,4620.800N,01410.500E,490m,1
+-----Related Tasks-----
+"Local","Lesce Bled","Lesce Bled","Bled","Kranj","Lesce Bled","Lesce Bled"
+"Triangle","Lesce Bled","Lesce Bled","Radovljica","Kranj","Lesce Bled","Lesce Bled"
+Options,TaskTime=02:30:00
+"Added","Lesce Bled","Lesce Bled","Bled","Lesce Bled","Lesce Bled"
+"#;
+
+#[test]
+fn test_identical_files() {
+    let cup = parse(OLD);
+    assert!(cup.diff(&cup.clone()).is_empty());
+}
+
+#[test]
+fn test_diff_data() {
+    let diff = parse(OLD).diff(&parse(NEW));
+
+    let WaypointChange::Modified(kranj) = &diff.waypoints[0] else {
+        panic!("unexpected change: {:?}", diff.waypoints[0]);
+    };
+    let moved = kranj.moved.as_ref().map(|d| d.to_meters().round());
+    assert_eq!(moved, Some(185.0));
+    assert!(kranj.fields.is_empty());
+
+    let WaypointChange::Modified(bled) = &diff.waypoints[2] else {
+        panic!("unexpected change: {:?}", diff.waypoints[2]);
+    };
+    assert_eq!(bled.fields[0].column, Column::Style);
+
+    assert_eq!(
+        diff.tasks[0],
+        TaskChange::Modified {
+            task: "Triangle".to_string(),
+            parts: vec![TaskPart::Points, TaskPart::Options],
+        }
+    );
+}
+
+#[test]
+fn test_diff_text() {
+    let diff = parse(OLD).diff(&parse(NEW));
+
+    assert_snapshot!(diff, @r"
+    ~ Waypoint 'Kranj': moved by 185m
+    ~ Waypoint 'Lesce': renamed to 'Lesce Bled'
+    ~ Waypoint 'Bled': style: '1' -> '3'
+    + Waypoint 'Radovljica'
+    - Waypoint 'Begunje'
+    ~ Task 'Triangle': points, options
+    + Task 'Added'
+    - Task 'Removed'
+    ");
+}
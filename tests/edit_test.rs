@@ -0,0 +1,139 @@
+mod common;
+
+use claims::{assert_err, assert_ok};
+use common::{names, parse};
+use insta::assert_snapshot;
+use seeyou_cup::{RemovePolicy, Waypoint};
+
+const INPUT: &str = r#"name,code,country,lat,lon,elev,style
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5
+"Bled","BLED",SI,4622.100N,01406.820E,501m,1
+"Radovljica","RADO",SI,4620.800N,01410.500E,490m,1
+"Kranj","KRAN",SI,4614.000N,01421.000E,386m,1
+-----Related Tasks-----
+"Local","Lesce","Bled","Radovljica","Kranj","Bled","Lesce"
+ObsZone=0,Style=2,R1=500m,A1=180,Line=1
+ObsZone=1,Style=1,R1=1000m
+ObsZone=2,Style=1,R1=2000m
+ObsZone=3,Style=3,R1=3000m
+STARTS="Bled","Kranj"
+"Inline","Lesce","Lesce","Radovljica","Lesce","Lesce"
+Point=2,"Radovljica","INL",SI,4621.000N,01411.000E,490m,1
+"#;
+
+#[test]
+fn test_rename_waypoint() {
+    let mut cup = parse(INPUT);
+    assert_ok!(cup.rename_waypoint("Bled", "Bled Lake"));
+
+    assert_eq!(names(&cup), ["Lesce", "Bled Lake", "Radovljica", "Kranj"]);
+    let task = &cup.tasks[0];
+    assert_eq!(
+        task.waypoint_names,
+        [
+            "Lesce",
+            "Bled Lake",
+            "Radovljica",
+            "Kranj",
+            "Bled Lake",
+            "Lesce"
+        ]
+    );
+    assert_eq!(task.multiple_starts, ["Bled Lake", "Kranj"]);
+
+    // Inline points don't refer to the waypoint list
+    assert_ok!(cup.rename_waypoint("Radovljica", "Radol'ca"));
+    assert_eq!(cup.tasks[0].waypoint_names[2], "Radol'ca");
+    assert_eq!(cup.tasks[1].waypoint_names[2], "Radovljica");
+}
+
+#[test]
+fn test_rename_waypoint_errors() {
+    let mut cup = parse(INPUT);
+
+    let unknown = assert_err!(cup.rename_waypoint("Bohinj", "Bohinj Lake"));
+    let duplicate = assert_err!(cup.rename_waypoint("Bled", "Lesce"));
+    assert_snapshot!(unknown, @"Unknown waypoint: 'Bohinj'");
+    assert_snapshot!(duplicate, @"Duplicate waypoint: 'Lesce'");
+    assert_eq!(cup, parse(INPUT));
+}
+
+#[test]
+fn test_remove_waypoint_refuse() {
+    let mut cup = parse(INPUT);
+
+    let error = assert_err!(cup.remove_waypoint("Kranj", RemovePolicy::Refuse));
+    assert_snapshot!(error, @"Waypoint is used by a task: 'Kranj'");
+    assert_eq!(cup, parse(INPUT));
+
+    // Only the inline point of the second task is named Radovljica
+    cup.tasks.remove(0);
+    let removed = assert_ok!(cup.remove_waypoint("Radovljica", RemovePolicy::Refuse));
+    assert_eq!(removed.code, "RADO");
+    assert_eq!(names(&cup), ["Lesce", "Bled", "Kranj"]);
+}
+
+#[test]
+fn test_remove_waypoint_drop_from_tasks() {
+    let mut cup = parse(INPUT);
+    assert_ok!(cup.remove_waypoint("Radovljica", RemovePolicy::DropFromTasks));
+
+    let task = &cup.tasks[0];
+    assert_eq!(
+        task.waypoint_names,
+        ["Lesce", "Bled", "Kranj", "Bled", "Lesce"]
+    );
+    let zones = task.observation_zones.iter().map(|zone| zone.index);
+    assert_eq!(zones.collect::<Vec<_>>(), [0, 1, 2]);
+    assert_eq!(task.multiple_starts, ["Bled", "Kranj"]);
+
+    // The inline point remains
+    assert_eq!(cup.tasks[1], parse(INPUT).tasks[1]);
+
+    // Removed takeoff and landing leave an empty name
+    assert_ok!(cup.remove_waypoint("Lesce", RemovePolicy::DropFromTasks));
+    assert_eq!(
+        cup.tasks[0].waypoint_names,
+        ["", "Bled", "Kranj", "Bled", ""]
+    );
+}
+
+#[test]
+fn test_remove_waypoint_inline() {
+    let mut cup = parse(INPUT);
+    let removed = assert_ok!(cup.remove_waypoint("Bled", RemovePolicy::Inline));
+
+    let task = &cup.tasks[0];
+    let original = &parse(INPUT).tasks[0];
+    assert_eq!(task.waypoint_names, original.waypoint_names);
+    assert_eq!(task.observation_zones, original.observation_zones);
+    assert_eq!(task.points, [(1, removed.clone()), (4, removed)]);
+    assert_eq!(task.multiple_starts, ["Kranj"]);
+
+    // The task survives writing without the waypoint
+    let output = assert_ok!(cup.to_string());
+    assert_eq!(parse(&output).tasks, cup.tasks);
+}
+
+#[test]
+fn test_replace_waypoint() {
+    let mut cup = parse(INPUT);
+    let waypoint = Waypoint {
+        name: "Kranj Airfield".to_string(),
+        code: "LJKR".to_string(),
+        ..cup.waypoints[3].clone()
+    };
+
+    let previous = assert_ok!(cup.replace_waypoint("Kranj", waypoint.clone()));
+    assert_eq!(previous.name, "Kranj");
+    assert_eq!(cup.waypoints[3], waypoint);
+    assert_eq!(cup.tasks[0].waypoint_names[3], "Kranj Airfield");
+    assert_eq!(cup.tasks[0].multiple_starts, ["Bled", "Kranj Airfield"]);
+
+    let waypoint = Waypoint {
+        name: "Lesce".to_string(),
+        ..waypoint
+    };
+    let error = assert_err!(cup.replace_waypoint("Kranj Airfield", waypoint));
+    assert_snapshot!(error, @"Duplicate waypoint: 'Lesce'");
+}
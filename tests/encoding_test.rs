@@ -1,7 +1,7 @@
-use claims::assert_ok;
-use insta::assert_snapshot;
-use seeyou_cup::CupFile;
+use claims::{assert_err, assert_ok, assert_some_eq};
+use insta::{assert_debug_snapshot, assert_snapshot};
 use seeyou_cup::Encoding::{self, Utf8, Windows1252};
+use seeyou_cup::{CupFile, EncodingFallback, WarningKind};
 use std::path::{Path, PathBuf};
 
 const FIXTURES: [(&str, Encoding); 4] = [
@@ -74,3 +74,84 @@ fn test_all_fixtures_parse_auto_detect() {
         assert!(!cup.waypoints.is_empty(), "No waypoints in {}", fixture);
     }
 }
+
+const POLISH_INPUT: &str = r#"name,code,country,lat,lon,elev,style
+"Łódź Lublinek","EPLL",PL,5143.302N,01923.885E,184m,5
+"Zürich","ZUR",CH,4722.614N,00832.502E,408m,1
+-----Related Tasks-----
+"Przełęcz","Łódź Lublinek","Zürich","Łódź Lublinek"
+"#;
+
+#[test]
+fn test_write_windows1252_fallback_error() {
+    let (cup, _) = assert_ok!(CupFile::from_str(POLISH_INPUT));
+
+    let mut buffer = Vec::new();
+    let err =
+        assert_err!(cup.to_writer_with_fallback(&mut buffer, Windows1252, EncodingFallback::Error));
+    assert_snapshot!(err, @"Encoding error: Failed to encode with Windows1252");
+}
+
+#[test]
+fn test_write_windows1252_fallback_replace() {
+    let (cup, _) = assert_ok!(CupFile::from_str(POLISH_INPUT));
+
+    let mut buffer = Vec::new();
+    let warnings = assert_ok!(cup.to_writer_with_fallback(
+        &mut buffer,
+        Windows1252,
+        EncodingFallback::Replace
+    ));
+    assert_debug_snapshot!(warnings.iter().map(|w| w.message()).collect::<Vec<_>>(), @r#"
+    [
+        "Replaced characters unsupported by Windows1252 in waypoint 'Łódź Lublinek'",
+        "Replaced characters unsupported by Windows1252 in task 'Przełęcz'",
+    ]
+    "#);
+
+    let (parsed, _) = assert_ok!(CupFile::from_reader_with_encoding(&buffer[..], Windows1252));
+    assert_eq!(parsed.waypoints[0].name, "?ód? Lublinek");
+    assert_eq!(parsed.waypoints[1].name, "Zürich");
+    assert_eq!(parsed.tasks[0].waypoint_names[0], "?ód? Lublinek");
+}
+
+#[test]
+fn test_write_windows1252_fallback_transliterate() {
+    let (cup, _) = assert_ok!(CupFile::from_str(POLISH_INPUT));
+
+    let mut buffer = Vec::new();
+    let warnings = assert_ok!(cup.to_writer_with_fallback(
+        &mut buffer,
+        Windows1252,
+        EncodingFallback::Transliterate
+    ));
+    assert_eq!(warnings.len(), 2);
+    assert!(
+        warnings
+            .iter()
+            .all(|w| w.kind() == WarningKind::EncodingFallback)
+    );
+    assert!(warnings.iter().all(|w| w.line().is_none()));
+
+    let (parsed, _) = assert_ok!(CupFile::from_reader_with_encoding(&buffer[..], Windows1252));
+    assert_eq!(parsed.waypoints[0].name, "Lódz Lublinek");
+    assert_eq!(parsed.waypoints[1].name, "Zürich");
+
+    let task = &parsed.tasks[0];
+    assert_some_eq!(&task.description, "Przelecz");
+    assert_eq!(task.waypoint_names[0], parsed.waypoints[0].name);
+    assert_eq!(task.waypoint_names[2], parsed.waypoints[0].name);
+}
+
+#[test]
+fn test_write_utf8_fallback_is_noop() {
+    let (cup, _) = assert_ok!(CupFile::from_str(POLISH_INPUT));
+
+    let mut buffer = Vec::new();
+    let warnings =
+        assert_ok!(cup.to_writer_with_fallback(&mut buffer, Utf8, EncodingFallback::Transliterate));
+    assert_eq!(warnings.len(), 0);
+
+    let (parsed, _) = assert_ok!(CupFile::from_reader(&buffer[..]));
+    assert_eq!(parsed, cup);
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use claims::{assert_err, assert_ok};
+use common::{names, parse};
+use insta::assert_snapshot;
+use seeyou_cup::{CupFile, Distance};
+
+const INPUT: &str = r#"name,code,country,lat,lon,elev,style,rwdir,rwlen,freq,desc
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5,130,1140m,123.500,
+"Bled","BLED",SI,4622.100N,01406.820E,501m,1,,,,
+"Bohinj","BOH",SI,4617.000N,01352.000E,520m,1,,,,
+"Field A","FA",SI,4620.000N,01400.000E,500m,3,,,,
+"Field B","FB",SI,4640.000N,01400.000E,600m,3,,,,
+"Kranj","KRAN",SI,4614.000N,01421.000E,386m,1,,,,
+"Ljubljana","LJLJ",SI,4613.420N,01427.460E,388m,5,,,,
+"Tower","TWR",SI,4617.000N,01405.000E,900m,8,,,,
+-----Related Tasks-----
+"Day 1","Lesce","Lesce","Bohinj","Inline","Lesce","Lesce"
+Point=3,"Inline","INL",SI,4625.000N,01415.000E,800m,1
+STARTS="Bled"
+"Day 2","Lesce","Lesce","Kranj","Lesce","Lesce"
+"Broken","Lesce","Lesce","Unknown","Lesce","Lesce"
+"#;
+
+#[test]
+fn test_extract_task() {
+    let cup = parse(INPUT);
+    let day = assert_ok!(cup.extract_task(0));
+
+    assert_eq!(names(&day), ["Lesce", "Bled", "Bohinj"]);
+    assert_eq!(day.tasks, [cup.tasks[0].clone()]);
+    assert_eq!(day.columns, cup.columns);
+
+    // The extracted file resolves on its own
+    assert_ok!(day.tasks[0].resolve(&day.waypoints));
+    assert!(day.validate().is_empty());
+}
+
+#[test]
+fn test_extract_task_with_landables() {
+    let cup = parse(INPUT);
+
+    // Field A lies within 2 km of the course, Field B about 30 km north of
+    // it, and Ljubljana 8 km beyond Kranj
+    let day = assert_ok!(cup.extract_task_with_landables(0, &Distance::Kilometers(10.0)));
+    assert_eq!(names(&day), ["Lesce", "Bled", "Bohinj", "Field A"]);
+
+    let day = assert_ok!(cup.extract_task_with_landables(1, &Distance::Kilometers(10.0)));
+    assert_eq!(names(&day), ["Lesce", "Kranj", "Ljubljana"]);
+
+    let day = assert_ok!(cup.extract_task_with_landables(1, &Distance::Kilometers(1.0)));
+    assert_eq!(names(&day), ["Lesce", "Kranj"]);
+}
+
+#[test]
+fn test_extract_task_errors() {
+    let cup = parse(INPUT);
+
+    let unknown_task = assert_err!(cup.extract_task(3));
+    let unknown_waypoint = assert_err!(cup.extract_task(2));
+    assert_snapshot!(unknown_task, @"Unknown task index: 3");
+    assert_snapshot!(unknown_waypoint, @"Unknown waypoint: 'Unknown'");
+}
+
+#[test]
+fn test_extract_fixture_task() {
+    let path = "tests/fixtures/709-km-Dreieck-DMSt-Aachen-Stolberg-TV.cup";
+    let (cup, _) = assert_ok!(CupFile::from_path(path));
+
+    let task = assert_ok!(cup.extract_task(0));
+    assert_eq!(task.waypoints, cup.waypoints);
+
+    let output = assert_ok!(task.to_string());
+    assert_eq!(parse(&output).tasks, cup.tasks);
+}
@@ -0,0 +1,218 @@
+mod common;
+
+use claims::{assert_err, assert_ok, assert_ok_eq};
+use common::{names, parse};
+use insta::assert_snapshot;
+use seeyou_cup::{
+    Comparison, Coordinate, CupFile, Distance, Elevation, TaskFilter, WaypointFilter, WaypointStyle,
+};
+
+const INPUT: &str = r#"name,code,country,lat,lon,elev,style
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5
+"Bled","BLED",SI,4622.100N,01406.820E,501m,1
+"Aalen Heidenheim","EDPA",DE,4846.680N,01015.870E,585m,5
+"Hornberg","HORN",DE,4845.500N,00953.600E,2100ft,4
+"Outlanding Ost","OUT1",DE,4840.000N,01010.000E,450m,3
+"Klippeneck","KLIP",DE,4806.380N,00845.530E,973m,2
+-----Related Tasks-----
+"Slovenia","Lesce","Lesce","Bled","Lesce","Lesce"
+"Swabia","Aalen Heidenheim","Aalen Heidenheim","Hornberg","Outlanding Ost","Klippeneck","Aalen Heidenheim","Aalen Heidenheim"
+ObsZone=1,Style=1,R1=500m
+ObsZone=2,Style=1,R1=1000m
+ObsZone=3,Style=1,R1=1500m
+ObsZone=4,Style=1,R1=2000m
+"Inline","Aalen Heidenheim","Aalen Heidenheim","Remote","Bled","Aalen Heidenheim","Aalen Heidenheim"
+Point=2,"Remote","REM",DE,4850.000N,01020.000E,500m,1
+"#;
+
+fn coordinate(latitude: f64, longitude: f64) -> Coordinate {
+    assert_ok!(Coordinate::new(latitude, longitude))
+}
+
+fn query(cup: &CupFile, query: &str) -> Vec<String> {
+    let filter: WaypointFilter = assert_ok!(query.parse());
+    let filtered = cup.filter(&filter, TaskFilter::Keep);
+    names(&filtered).into_iter().map(String::from).collect()
+}
+
+#[test]
+fn test_query_example() {
+    let filter: WaypointFilter = assert_ok!(r#"style in [2,3,4,5] and country = "de""#.parse());
+    assert_eq!(
+        filter,
+        WaypointFilter::And(vec![
+            WaypointFilter::Style(vec![
+                WaypointStyle::GrassAirfield,
+                WaypointStyle::Outlanding,
+                WaypointStyle::GlidingAirfield,
+                WaypointStyle::SolidAirfield,
+            ]),
+            WaypointFilter::Country("de".to_string()),
+        ])
+    );
+
+    let cup = parse(INPUT);
+    let filtered = cup.filter(&filter, TaskFilter::Keep);
+    assert_eq!(
+        names(&filtered),
+        [
+            "Aalen Heidenheim",
+            "Hornberg",
+            "Outlanding Ost",
+            "Klippeneck"
+        ]
+    );
+    assert_eq!(filtered.tasks, cup.tasks);
+}
+
+#[test]
+fn test_query_fields() {
+    let cup = parse(INPUT);
+
+    assert_eq!(query(&cup, "name = 'a*'"), ["Aalen Heidenheim"]);
+    assert_eq!(
+        query(&cup, "name = B?ed or code = KLIP"),
+        ["Bled", "Klippeneck"]
+    );
+    assert_eq!(query(&cup, "country in [si]"), ["Lesce", "Bled"]);
+    assert_eq!(query(&cup, "elev > 600m"), ["Hornberg", "Klippeneck"]);
+    assert_eq!(
+        query(&cup, "landable and not (country = DE and elev >= 2000ft)"),
+        ["Lesce", "Aalen Heidenheim", "Outlanding Ost"]
+    );
+    assert_eq!(query(&cup, "style != 1 and country != DE"), ["Lesce"]);
+}
+
+#[test]
+fn test_query_errors() {
+    let errors = [
+        "style =",
+        "elev ~ 300m",
+        "height > 300m",
+        "name > abc",
+        "style in [1, unknown]",
+        "(landable",
+        "landable landable",
+        "elev in [1m, 2m]",
+        "name = \"Bled",
+    ]
+    .map(|query| assert_err!(query.parse::<WaypointFilter>()));
+
+    assert_snapshot!(errors.join("\n"), @r"
+    Invalid query: unexpected end
+    Invalid query: unexpected character '~'
+    Invalid query: unknown field 'height'
+    Invalid query: 'name' only supports '=' and '!='
+    Invalid query: unknown style 'unknown'
+    Invalid query: unexpected end
+    Invalid query: unexpected 'landable'
+    Invalid query: 'elev' can't be compared to a list
+    Invalid query: unterminated string
+    ");
+
+    let nested = format!("{}landable{}", "(".repeat(100), ")".repeat(100));
+    let error = assert_err!(nested.parse::<WaypointFilter>());
+    assert_snapshot!(error, @"Invalid query: too deeply nested");
+}
+
+#[test]
+fn test_query_many_negations() {
+    let even = format!("{}landable", "not ".repeat(100_000));
+    let odd = format!("not {even}");
+    assert_ok_eq!(even.parse::<WaypointFilter>(), WaypointFilter::Landable);
+    assert_ok_eq!(odd.parse::<WaypointFilter>(), !WaypointFilter::Landable);
+}
+
+#[test]
+fn test_filter_composition() {
+    let cup = parse(INPUT);
+
+    let south_west = coordinate(48.5, 9.5);
+    let north_east = coordinate(49.0, 10.5);
+    let filter = WaypointFilter::BoundingBox {
+        south_west,
+        north_east,
+    }
+    .and(!WaypointFilter::Elevation(
+        Comparison::Less,
+        Elevation::Meters(500.0),
+    ));
+    let filtered = cup.filter(&filter, TaskFilter::Keep);
+    assert_eq!(names(&filtered), ["Aalen Heidenheim", "Hornberg"]);
+
+    // Triangle around Lesce and Bled
+    let filter = WaypointFilter::Polygon(vec![
+        coordinate(46.0, 14.0),
+        coordinate(46.5, 14.5),
+        coordinate(46.5, 13.5),
+    ]);
+    let filtered = cup.filter(&filter, TaskFilter::Keep);
+    assert_eq!(names(&filtered), ["Lesce", "Bled"]);
+}
+
+#[test]
+fn test_filter_drop_tasks() {
+    let cup = parse(INPUT);
+    let filter = WaypointFilter::Name("Outlanding*".to_string());
+
+    let filtered = cup.filter(&!filter, TaskFilter::Drop);
+    let descriptions = filtered.tasks.iter().map(|t| t.description.as_deref());
+    assert_eq!(
+        descriptions.collect::<Vec<_>>(),
+        [Some("Slovenia"), Some("Inline")]
+    );
+}
+
+#[test]
+fn test_filter_prune_tasks() {
+    let cup = parse(INPUT);
+    let filter = WaypointFilter::Country("DE".to_string());
+
+    let filtered = cup.filter(&filter.clone(), TaskFilter::Prune);
+    assert_eq!(filtered.tasks.len(), 2);
+
+    // All points remain in the Swabia task
+    assert_eq!(filtered.tasks[0], cup.tasks[1]);
+
+    // Bled is removed, the inline point remains
+    let task = &filtered.tasks[1];
+    assert_eq!(
+        task.waypoint_names,
+        [
+            "Aalen Heidenheim",
+            "Aalen Heidenheim",
+            "Remote",
+            "Aalen Heidenheim",
+            "Aalen Heidenheim"
+        ]
+    );
+    assert_eq!(task.points[0].0, 2);
+
+    // Removing a turnpoint shifts the zones of the following points
+    let filter = filter.and(!WaypointFilter::Code("HORN".to_string()));
+    let filtered = cup.filter(&filter, TaskFilter::Prune);
+    let task = &filtered.tasks[0];
+    assert_eq!(
+        task.waypoint_names,
+        [
+            "Aalen Heidenheim",
+            "Aalen Heidenheim",
+            "Outlanding Ost",
+            "Klippeneck",
+            "Aalen Heidenheim",
+            "Aalen Heidenheim"
+        ]
+    );
+    let zones = task
+        .observation_zones
+        .iter()
+        .map(|z| (z.index, z.r1.clone()));
+    assert_eq!(
+        zones.collect::<Vec<_>>(),
+        [
+            (1, Some(Distance::Meters(1000.0))),
+            (2, Some(Distance::Meters(1500.0))),
+            (3, Some(Distance::Meters(2000.0))),
+        ]
+    );
+}
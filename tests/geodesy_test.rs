@@ -0,0 +1,112 @@
+use claims::assert_ok;
+use seeyou_cup::geodesy::EarthModel;
+use seeyou_cup::{Coordinate, CupFile, Distance};
+
+fn dms(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+    degrees.signum() * (degrees.abs() + minutes / 60.0 + seconds / 3600.0)
+}
+
+fn flinders_peak() -> Coordinate {
+    assert_ok!(Coordinate::new(
+        dms(-37.0, 57.0, 3.72030),
+        dms(144.0, 25.0, 29.52440)
+    ))
+}
+
+fn buninyong() -> Coordinate {
+    assert_ok!(Coordinate::new(
+        dms(-37.0, 39.0, 10.15610),
+        dms(143.0, 55.0, 35.38390)
+    ))
+}
+
+#[test]
+fn test_wgs84_inverse() {
+    // Reference values from Vincenty (1975)
+    let distance = flinders_peak().distance_to(&buninyong());
+    assert!((distance.to_meters() - 54_972.271).abs() < 0.001);
+
+    let bearing = flinders_peak().bearing_to(&buninyong());
+    assert!((bearing - dms(306.0, 52.0, 5.37)).abs() < 1e-5);
+}
+
+#[test]
+fn test_wgs84_direct() {
+    let distance = Distance::Meters(54_972.271);
+    let destination = flinders_peak().destination(dms(306.0, 52.0, 5.37), &distance);
+    assert!((destination.latitude() - buninyong().latitude()).abs() < 1e-7);
+    assert!((destination.longitude() - buninyong().longitude()).abs() < 1e-7);
+}
+
+#[test]
+fn test_spherical_is_close_to_wgs84() {
+    let wgs84 = EarthModel::Wgs84.distance(&flinders_peak(), &buninyong());
+    let spherical = EarthModel::Spherical.distance(&flinders_peak(), &buninyong());
+    let error = (spherical.to_meters() - wgs84.to_meters()).abs() / wgs84.to_meters();
+    assert!(error < 0.005);
+
+    let bearing = EarthModel::Spherical.bearing(&flinders_peak(), &buninyong());
+    assert!((bearing - dms(306.0, 52.0, 5.37)).abs() < 0.5);
+
+    let destination = EarthModel::Spherical.destination(&flinders_peak(), bearing, &spherical);
+    assert!((destination.latitude() - buninyong().latitude()).abs() < 1e-7);
+    assert!((destination.longitude() - buninyong().longitude()).abs() < 1e-7);
+}
+
+#[test]
+fn test_coincident_and_antipodal_points() {
+    let point = assert_ok!(Coordinate::new(46.0, 14.0));
+    assert_eq!(point.distance_to(&point).to_meters(), 0.0);
+
+    // Vincenty's formula does not converge here, the geodesic runs over the pole
+    let antipode = assert_ok!(Coordinate::new(-46.0, -166.0));
+    let distance = point.distance_to(&antipode).to_meters();
+    assert!((distance - 20_003_931.458_6).abs() < 0.001);
+}
+
+#[test]
+fn test_nearly_antipodal_points() {
+    // Reference values from GeographicLib
+    let origin = assert_ok!(Coordinate::new(0.0, 0.0));
+    for (lat, lon, expected) in [(0.5, 179.5, 19_936_288.579), (0.5, 179.7, 19_944_127.421)] {
+        let point = assert_ok!(Coordinate::new(lat, lon));
+        let distance = origin.distance_to(&point);
+        assert!((distance.to_meters() - expected).abs() < 0.001);
+        assert!((point.distance_to(&origin).to_meters() - expected).abs() < 0.001);
+
+        // The bearing leads to the point
+        let destination = origin.destination(origin.bearing_to(&point), &distance);
+        assert!((destination.latitude() - lat).abs() < 1e-9);
+        assert!((destination.longitude() - lon).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_destination_across_antimeridian() {
+    let point = assert_ok!(Coordinate::new(0.0, 179.9));
+    let destination = point.destination(90.0, &Distance::Kilometers(22.0));
+    assert!(destination.longitude() < -179.9);
+    assert!(destination.latitude().abs() < 1e-9);
+}
+
+#[test]
+fn test_waypoint_leg() {
+    let input = r#"name,code,country,lat,lon,elev,style
+"Aachen Stolberg TV","ACSTOTV",de,5045.350N,00616.850E,300m,1
+"Helmstadter Berg","HELMSTB",de,5212.450N,01100.800E,200m,1
+"#;
+
+    let (cup, _) = assert_ok!(CupFile::from_str(input));
+    let (from, to) = (&cup.waypoints[0], &cup.waypoints[1]);
+
+    let distance = from.distance_to(to);
+    assert!((distance.to_meters() - 366_192.3).abs() < 0.1);
+    assert!((to.distance_to(from).to_meters() - distance.to_meters()).abs() < 1e-6);
+
+    let bearing = from.bearing_to(to);
+    assert!((60.0..65.0).contains(&bearing), "{bearing}");
+
+    let destination = from.destination(bearing, &distance);
+    assert!((destination.latitude() - to.latitude).abs() < 1e-7);
+    assert!((destination.longitude() - to.longitude).abs() < 1e-7);
+}
@@ -0,0 +1,360 @@
+#![cfg(feature = "geojson")]
+
+mod common;
+
+use claims::{assert_err, assert_none, assert_ok, assert_some, assert_some_eq};
+use common::{is_counterclockwise, parse};
+use insta::assert_snapshot;
+use seeyou_cup::{CupFile, Elevation, GeoJsonOptions, WaypointStyle};
+use serde_json::{Value, json};
+
+const INPUT: &str = r#"name,code,country,lat,lon,elev,style,rwdir,rwlen,rwwidth,freq,desc,userdata,pics
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5,130,1140m,30m,123.500,"Grass & asphalt","Club","lesce.jpg;runway.jpg"
+"Bled","BLED",SI,4622.100N,01406.820E,1644ft,1,,,,,,,
+"Bohinj","BOH",SI,4617.000N,01352.000E,520m,7,,,,,,,
+-----Related Tasks-----
+"Day 1","Lesce","Lesce","Bohinj","Bled","Lesce","Lesce"
+Options,NoStart=12:00:00,TaskTime=02:30:00,WpDis=True,NearDis=0.5km
+ObsZone=0,Style=2,R1=5000m,A1=180,Line=True
+ObsZone=1,Style=1,R1=500m,A1=45,R2=200m,A2=180,A12=90
+Point=3,"Bled","BLD2",SI,4625.000N,01415.000E,800m,1,,,,,,,
+STARTS="Bled","Bohinj"
+"",,"Bled","Bohinj",""
+"#;
+
+#[test]
+fn test_geojson_roundtrip() {
+    let cup = parse(INPUT);
+
+    let geojson = assert_ok!(cup.to_geojson_string(&GeoJsonOptions::default()));
+    let (roundtrip, warnings) = assert_ok!(CupFile::from_geojson_str(&geojson));
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(roundtrip, cup);
+}
+
+#[test]
+fn test_geojson_fixture_roundtrip() {
+    let options = GeoJsonOptions {
+        observation_zones: true,
+    };
+    for path in [
+        "tests/fixtures/709-km-Dreieck-DMSt-Aachen-Stolberg-TV.cup",
+        "tests/fixtures/2018_schwarzwald_landefelder.cup",
+        "tests/fixtures/EC25.cup",
+    ] {
+        let (cup, _) = assert_ok!(CupFile::from_path(path));
+
+        let geojson = assert_ok!(cup.to_geojson_string(&options));
+        let (roundtrip, warnings) = assert_ok!(CupFile::from_geojson_str(&geojson));
+        assert!(warnings.is_empty(), "{path}: {warnings:?}");
+        assert_eq!(roundtrip, cup, "{path}");
+    }
+}
+
+#[test]
+fn test_write_geojson() {
+    let mut cup = parse(INPUT);
+    cup.waypoints.truncate(1);
+    cup.tasks.clear();
+
+    let geojson = assert_ok!(cup.to_geojson_string(&GeoJsonOptions::default()));
+    assert_snapshot!(geojson, @r#"
+    {
+      "columns": [
+        "name",
+        "code",
+        "country",
+        "lat",
+        "lon",
+        "elev",
+        "style",
+        "rwdir",
+        "rwlen",
+        "rwwidth",
+        "freq",
+        "desc",
+        "userdata",
+        "pics"
+      ],
+      "features": [
+        {
+          "geometry": {
+            "coordinates": [
+              14.1745,
+              46.35633333333333,
+              504.0
+            ],
+            "type": "Point"
+          },
+          "properties": {
+            "code": "LJBL",
+            "country": "SI",
+            "description": "Grass & asphalt",
+            "elevation": "504m",
+            "frequency": "123.500",
+            "kind": "waypoint",
+            "name": "Lesce",
+            "pictures": [
+              "lesce.jpg",
+              "runway.jpg"
+            ],
+            "runway_direction": 130,
+            "runway_length": "1140m",
+            "runway_width": "30m",
+            "style": 5,
+            "userdata": "Club"
+          },
+          "type": "Feature"
+        }
+      ],
+      "type": "FeatureCollection"
+    }
+    "#);
+}
+
+#[test]
+fn test_write_geojson_task() {
+    let mut cup = parse(INPUT);
+    cup.tasks.truncate(1);
+
+    let geojson = assert_ok!(cup.to_geojson_string(&GeoJsonOptions::default()));
+    let collection: Value = assert_ok!(serde_json::from_str(&geojson));
+    let mut task = collection["features"][3].clone();
+
+    // The course goes from start to finish, through the inline point
+    let course = assert_some!(task["geometry"]["coordinates"].as_array());
+    assert_eq!(course.len(), 4);
+    assert_eq!(course[2], json!([14.25, 46.416666666666664]));
+
+    let properties = assert_some!(task["properties"].as_object_mut());
+    let points = assert_some!(properties.remove("points"));
+    assert_eq!(points[0]["index"], 3);
+    assert_eq!(points[0]["waypoint"]["properties"]["code"], "BLD2");
+    assert_snapshot!(assert_ok!(serde_json::to_string_pretty(properties)), @r#"
+    {
+      "description": "Day 1",
+      "kind": "task",
+      "multiple_starts": [
+        "Bled",
+        "Bohinj"
+      ],
+      "observation_zones": [
+        {
+          "a1": 180.0,
+          "a12": null,
+          "a2": null,
+          "index": 0,
+          "line": true,
+          "r1": "5000m",
+          "r2": null,
+          "style": 2
+        },
+        {
+          "a1": 45.0,
+          "a12": 90.0,
+          "a2": 180.0,
+          "index": 1,
+          "line": null,
+          "r1": "500m",
+          "r2": "200m",
+          "style": 1
+        }
+      ],
+      "options": {
+        "after_pts": null,
+        "before_pts": null,
+        "bonus": null,
+        "max_pts": null,
+        "min_dis": null,
+        "near_alt": null,
+        "near_dis": "0.5km",
+        "no_start": "12:00:00",
+        "random_order": null,
+        "task_time": "02:30:00",
+        "wp_dis": true
+      },
+      "waypoint_names": [
+        "Lesce",
+        "Lesce",
+        "Bohinj",
+        "Bled",
+        "Lesce",
+        "Lesce"
+      ]
+    }
+    "#);
+}
+
+#[test]
+fn test_write_geojson_observation_zones() {
+    let mut cup = parse(INPUT);
+    cup.tasks.truncate(1);
+
+    let options = GeoJsonOptions {
+        observation_zones: true,
+    };
+    let geojson = assert_ok!(cup.to_geojson_string(&options));
+    assert_eq!(geojson.matches(r#""kind": "observation_zone""#).count(), 2);
+    assert_eq!(geojson.matches(r#""type": "Polygon""#).count(), 1);
+
+    // The start line and the task course
+    assert_eq!(geojson.matches(r#""type": "LineString""#).count(), 2);
+
+    // Polygon rings are counterclockwise
+    let collection: Value = assert_ok!(serde_json::from_str(&geojson));
+    let features = assert_some!(collection["features"].as_array());
+    let polygon = assert_some!(
+        features
+            .iter()
+            .find(|feature| feature["geometry"]["type"] == "Polygon")
+    );
+    let ring = assert_some!(polygon["geometry"]["coordinates"][0].as_array());
+    let positions = ring
+        .iter()
+        .map(|position| (position[0].as_f64().unwrap(), position[1].as_f64().unwrap()))
+        .collect::<Vec<_>>();
+    assert!(is_counterclockwise(&positions));
+
+    // Zones are ignored on import
+    let (roundtrip, warnings) = assert_ok!(CupFile::from_geojson_str(&geojson));
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(roundtrip, cup);
+
+    // Zones without a radius have no outline
+    cup.tasks[0].observation_zones[1].r1 = None;
+    let geojson = assert_ok!(cup.to_geojson_string(&options));
+    assert_eq!(geojson.matches(r#""kind": "observation_zone""#).count(), 1);
+}
+
+#[test]
+fn test_write_geojson_unknown_waypoint() {
+    let mut cup = parse(INPUT);
+    cup.tasks[0].waypoint_names[2] = "Unknown".to_string();
+
+    let error = assert_err!(cup.to_geojson_string(&GeoJsonOptions::default()));
+    assert_snapshot!(error, @"Unknown waypoint: 'Unknown'");
+}
+
+#[test]
+fn test_read_edited_geojson() {
+    let input = r#"{
+  "type": "FeatureCollection",
+  "features": [
+    {
+      "type": "Feature",
+      "geometry": { "type": "Point", "coordinates": [14.18, 46.36, 504] },
+      "properties": { "kind": "waypoint", "name": "Lesce", "style": "5", "elevation": "1654ft" }
+    },
+    {
+      "type": "Feature",
+      "geometry": { "type": "Point", "coordinates": [13.8667, 46.2833, 1520.5] },
+      "properties": { "name": "Vogel", "code": "VOG" }
+    },
+    {
+      "type": "Feature",
+      "geometry": { "type": "Point", "coordinates": [14.1137, 46.3683, 520] },
+      "properties": { "name": "Bled", "elevation": "501m" }
+    },
+    {
+      "type": "Feature",
+      "geometry": { "type": "LineString", "coordinates": [[14.18, 46.36], [13.8667, 46.2833]] },
+      "properties": { "waypoint_names": ["Lesce", "Lesce", "Vogel", "Lesce", "Lesce"] }
+    },
+    {
+      "type": "Feature",
+      "geometry": { "type": "Polygon", "coordinates": [] },
+      "properties": { "name": "Airspace" }
+    }
+  ]
+}"#;
+
+    let (cup, warnings) = assert_ok!(CupFile::from_geojson_str(input));
+    assert_eq!(cup.waypoints.len(), 3);
+
+    // The z coordinate was moved, so the elevation property is outdated
+    let warnings = warnings.iter().map(|warning| warning.message());
+    let warnings = warnings.collect::<Vec<_>>().join("\n");
+    assert_snapshot!(warnings, @"Feature 2: Ignored field: Elevation '501m' differs from z coordinate: '520'");
+
+    // The elevation property agrees with the z coordinate and keeps its unit
+    let lesce = &cup.waypoints[0];
+    assert_eq!(lesce.latitude, 46.36);
+    assert_eq!(lesce.longitude, 14.18);
+    assert_eq!(lesce.elevation, Elevation::Feet(1654.0));
+    assert_eq!(lesce.style, WaypointStyle::SolidAirfield);
+
+    // Missing properties get their default values
+    let vogel = &cup.waypoints[1];
+    assert_eq!(vogel.code, "VOG");
+    assert_eq!(vogel.elevation, Elevation::Meters(1520.5));
+    assert_eq!(vogel.style, WaypointStyle::Waypoint);
+    assert_eq!(vogel.country, "");
+
+    let bled = &cup.waypoints[2];
+    assert_eq!(bled.elevation, Elevation::Meters(520.0));
+
+    let task = &cup.tasks[0];
+    assert_none!(&task.description);
+    assert_none!(&task.options);
+    assert_eq!(
+        task.waypoint_names,
+        ["Lesce", "Lesce", "Vogel", "Lesce", "Lesce"]
+    );
+    assert_ok!(task.resolve(&cup.waypoints));
+}
+
+#[test]
+fn test_read_geojson_warnings() {
+    let input = r#"{
+  "type": "FeatureCollection",
+  "features": [
+    { "type": "Feature", "geometry": { "type": "Point", "coordinates": [14.17, 46.35] }, "properties": {} },
+    { "type": "Feature", "geometry": { "type": "Point", "coordinates": [14.17, 96.0] }, "properties": { "name": "North" } },
+    {
+      "type": "Feature",
+      "geometry": { "type": "Point", "coordinates": [14.17, 46.35] },
+      "properties": { "name": "Lesce", "style": 42, "runway_length": "long", "pictures": "lesce.jpg" }
+    },
+    {
+      "type": "Feature",
+      "geometry": null,
+      "properties": {
+        "kind": "task",
+        "description": "Day 1",
+        "waypoint_names": ["Lesce", "Lesce", "Lesce"],
+        "options": { "wp_dis": "maybe" },
+        "observation_zones": [{ "index": 0, "style": 9 }]
+      }
+    }
+  ]
+}"#;
+
+    let (cup, warnings) = assert_ok!(CupFile::from_geojson_str(input));
+    assert_eq!(cup.waypoints.len(), 1);
+    assert_eq!(cup.waypoints[0].style, WaypointStyle::Waypoint);
+    assert_eq!(cup.waypoints[0].runway_length, None);
+    assert_some_eq!(&cup.tasks[0].description, "Day 1");
+
+    let warnings = warnings
+        .iter()
+        .map(|warning| warning.message())
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert_snapshot!(warnings, @r#"
+    Feature 0: Name field cannot be empty
+    Feature 1: Invalid position: 'North'
+    Feature 2: Ignored field: Unknown waypoint style: '42'
+    Feature 2: Ignored field: Invalid runway_length: 'long'
+    Feature 2: Ignored field: Invalid pictures: '"lesce.jpg"'
+    Feature 3: Ignored field: Invalid wp_dis: 'maybe'
+    Feature 3: Ignored observation zone: Missing ObsZone index or style
+    "#);
+}
+
+#[test]
+fn test_read_geojson_errors() {
+    let not_collection = assert_err!(CupFile::from_geojson_str(r#"{"type": "Feature"}"#));
+    let invalid = assert_err!(CupFile::from_geojson_str("{"));
+    assert_snapshot!(not_collection, @"Parse error: Expected a GeoJSON FeatureCollection");
+    assert_snapshot!(invalid, @"JSON error: EOF while parsing an object at line 1 column 1");
+}
@@ -0,0 +1,230 @@
+#![cfg(feature = "gpx")]
+
+mod common;
+
+use claims::{assert_err, assert_ok, assert_some, assert_some_eq};
+use common::parse;
+use insta::assert_snapshot;
+use seeyou_cup::{CupFile, Elevation, WaypointStyle};
+
+const INPUT: &str = r#"name,code,country,lat,lon,elev,style,rwdir,rwlen,rwwidth,freq,desc,userdata,pics
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5,130,1140m,30m,123.500,"Grass & asphalt","Club","lesce.jpg;runway.jpg"
+"Bled","BLED",SI,4622.100N,01406.820E,1644ft,1,,,,,,,
+"Bohinj","BOH",SI,4617.000N,01352.000E,520m,7,,,,,,,
+-----Related Tasks-----
+"Day 1","Lesce","Lesce","Bohinj","Bled","Lesce","Lesce"
+Options,NoStart=12:00:00,TaskTime=02:30:00,WpDis=True,NearDis=0.5km
+ObsZone=0,Style=2,R1=5000m,A1=180,Line=True
+ObsZone=1,Style=1,R1=500m,A1=45,R2=200m,A2=180,A12=90
+Point=3,"Bled","BLD2",SI,4625.000N,01415.000E,800m,1,,,,,,,
+STARTS="Bled","Bohinj"
+"",,"Bled","Bohinj",""
+"#;
+
+#[test]
+fn test_gpx_roundtrip() {
+    let cup = parse(INPUT);
+
+    let gpx = assert_ok!(cup.to_gpx_string());
+    let (roundtrip, warnings) = assert_ok!(CupFile::from_gpx_str(&gpx));
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(roundtrip, cup);
+}
+
+#[test]
+fn test_gpx_fixture_roundtrip() {
+    for path in [
+        "tests/fixtures/709-km-Dreieck-DMSt-Aachen-Stolberg-TV.cup",
+        "tests/fixtures/2018_schwarzwald_landefelder.cup",
+        "tests/fixtures/EC25.cup",
+    ] {
+        let (cup, _) = assert_ok!(CupFile::from_path(path));
+
+        let gpx = assert_ok!(cup.to_gpx_string());
+        let (roundtrip, warnings) = assert_ok!(CupFile::from_gpx_str(&gpx));
+        assert!(warnings.is_empty(), "{path}: {warnings:?}");
+        assert_eq!(roundtrip, cup, "{path}");
+    }
+}
+
+#[test]
+fn test_write_gpx() {
+    let mut cup = parse(INPUT);
+    cup.waypoints.truncate(2);
+    cup.tasks.clear();
+
+    let gpx = assert_ok!(cup.to_gpx_string());
+    assert_snapshot!(gpx, @r#"
+    <?xml version="1.0" encoding="UTF-8"?>
+    <gpx version="1.1" creator="seeyou-cup" xmlns="http://www.topografix.com/GPX/1/1" xmlns:cup="https://github.com/glide-rs/seeyou-cup/gpx/1">
+      <metadata>
+        <extensions>
+          <cup:columns>name,code,country,lat,lon,elev,style,rwdir,rwlen,rwwidth,freq,desc,userdata,pics</cup:columns>
+        </extensions>
+      </metadata>
+      <wpt lat="46.35633333333333" lon="14.1745">
+        <ele>504</ele>
+        <name>Lesce</name>
+        <desc>Grass &amp; asphalt</desc>
+        <sym>Airport</sym>
+        <type>Airfield with solid surface runway</type>
+        <extensions>
+          <cup:code>LJBL</cup:code>
+          <cup:country>SI</cup:country>
+          <cup:style>5</cup:style>
+          <cup:rwdir>130</cup:rwdir>
+          <cup:rwlen>1140m</cup:rwlen>
+          <cup:rwwidth>30m</cup:rwwidth>
+          <cup:freq>123.500</cup:freq>
+          <cup:userdata>Club</cup:userdata>
+          <cup:pic>lesce.jpg</cup:pic>
+          <cup:pic>runway.jpg</cup:pic>
+        </extensions>
+      </wpt>
+      <wpt lat="46.36833333333333" lon="14.113666666666667">
+        <ele>501.0912</ele>
+        <name>Bled</name>
+        <sym>Waypoint</sym>
+        <type>Waypoint</type>
+        <extensions>
+          <cup:elev>1644ft</cup:elev>
+          <cup:code>BLED</cup:code>
+          <cup:country>SI</cup:country>
+          <cup:style>1</cup:style>
+        </extensions>
+      </wpt>
+    </gpx>
+    "#);
+}
+
+#[test]
+fn test_write_gpx_task() {
+    let mut cup = parse(INPUT);
+    cup.tasks.truncate(1);
+
+    let gpx = assert_ok!(cup.to_gpx_string());
+    let route = &gpx[assert_some!(gpx.find("<rte>"))..assert_some!(gpx.find("<rtept"))];
+    assert_snapshot!(route.trim_end(), @r#"
+    <rte>
+        <name>Day 1</name>
+        <extensions>
+          <cup:takeoff>Lesce</cup:takeoff>
+          <cup:landing>Lesce</cup:landing>
+          <cup:options no_start="12:00:00" task_time="02:30:00" wp_dis="true" near_dis="0.5km"/>
+          <cup:obszone index="0" style="2" r1="5000m" a1="180" line="true"/>
+          <cup:obszone index="1" style="1" r1="500m" a1="45" r2="200m" a2="180" a12="90"/>
+          <cup:start>Bled</cup:start>
+          <cup:start>Bohinj</cup:start>
+        </extensions>
+    "#);
+
+    // Only the inline point is marked as such
+    assert_eq!(gpx.matches("<rtept").count(), 4);
+    assert_eq!(gpx.matches("<cup:inline/>").count(), 1);
+}
+
+#[test]
+fn test_write_gpx_unknown_waypoint() {
+    let mut cup = parse(INPUT);
+    cup.tasks[0].waypoint_names[2] = "Unknown".to_string();
+
+    let error = assert_err!(cup.to_gpx_string());
+    assert_snapshot!(error, @"Unknown waypoint: 'Unknown'");
+}
+
+#[test]
+fn test_read_plain_gpx() {
+    let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="Garmin" xmlns="http://www.topografix.com/GPX/1/1">
+  <wpt lat="46.3563" lon="14.17450">
+    <ele>504.2</ele>
+    <name>Lesce</name>
+    <desc>Airfield</desc>
+    <sym>Airport</sym>
+  </wpt>
+  <wpt lat="46.2833" lon="13.8667">
+    <name>Bohinj</name>
+    <type>mountain top</type>
+  </wpt>
+  <wpt lat="46.36" lon="14.11">
+    <name>Bled</name>
+  </wpt>
+  <rte>
+    <name>Triangle</name>
+    <rtept lat="46.3563" lon="14.17450"><name>Lesce</name></rtept>
+    <rtept lat="46.2833" lon="13.8667"><name>Bohinj</name></rtept>
+    <rtept lat="46.4" lon="14.25"><name>Turnpoint</name><ele>800</ele></rtept>
+    <rtept lat="46.3563" lon="14.17450"><name>Lesce</name></rtept>
+  </rte>
+</gpx>"#;
+
+    let (cup, warnings) = assert_ok!(CupFile::from_gpx_str(input));
+    assert!(warnings.is_empty());
+
+    let lesce = &cup.waypoints[0];
+    assert_eq!(lesce.name, "Lesce");
+    assert_eq!(lesce.latitude, 46.3563);
+    assert_eq!(lesce.longitude, 14.1745);
+    assert_eq!(lesce.elevation, Elevation::Meters(504.2));
+    assert_eq!(lesce.description, "Airfield");
+    assert_eq!(lesce.style, WaypointStyle::SolidAirfield);
+    assert_eq!(cup.waypoints[1].style, WaypointStyle::MountainTop);
+    assert_eq!(cup.waypoints[2].style, WaypointStyle::Waypoint);
+    assert_eq!(cup.waypoints[2].elevation, Elevation::Meters(0.0));
+
+    // The route starts and ends at its first and last point, and points
+    // without a waypoint are defined inline
+    let task = &cup.tasks[0];
+    assert_some_eq!(&task.description, "Triangle");
+    assert_eq!(
+        task.waypoint_names,
+        ["Lesce", "Lesce", "Bohinj", "Turnpoint", "Lesce", "Lesce"]
+    );
+    assert_eq!(task.points.len(), 1);
+    assert_eq!(task.points[0].0, 3);
+    assert_eq!(task.points[0].1.elevation, Elevation::Meters(800.0));
+    assert_ok!(task.resolve(&cup.waypoints));
+}
+
+#[test]
+fn test_read_gpx_warnings() {
+    let input = r#"<gpx version="1.1" xmlns="http://www.topografix.com/GPX/1/1"
+     xmlns:cup="https://github.com/glide-rs/seeyou-cup/gpx/1">
+  <wpt lat="46.3563" lon="14.1745"></wpt>
+  <wpt lat="96.0" lon="14.1745"><name>North</name></wpt>
+  <wpt lat="46.3563" lon="14.1745">
+    <ele>high</ele>
+    <name>Lesce</name>
+    <extensions>
+      <cup:style>42</cup:style>
+      <cup:rwlen>long</cup:rwlen>
+    </extensions>
+  </wpt>
+</gpx>"#;
+
+    let (cup, warnings) = assert_ok!(CupFile::from_gpx_str(input));
+    assert_eq!(cup.waypoints.len(), 1);
+    assert_eq!(cup.waypoints[0].style, WaypointStyle::Waypoint);
+    assert_eq!(cup.waypoints[0].runway_length, None);
+
+    let warnings = warnings
+        .iter()
+        .map(|warning| format!("{}: {}", warning.line().unwrap(), warning.message()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert_snapshot!(warnings, @r"
+    3: Name field cannot be empty
+    4: Invalid position: 'North'
+    6: Ignored field: Invalid elevation: 'high'
+    9: Ignored field: Unknown waypoint style: '42'
+    10: Ignored field: Invalid rwlen: 'long'
+    ");
+}
+
+#[test]
+fn test_read_gpx_errors() {
+    let not_gpx = assert_err!(CupFile::from_gpx_str("<kml></kml>"));
+    let invalid = assert_err!(CupFile::from_gpx_str("<gpx>"));
+    assert_snapshot!(not_gpx, @"Parse error: Missing <gpx> root element");
+    assert_snapshot!(invalid, @"XML error: the root node was opened but never closed");
+}
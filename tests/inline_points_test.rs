@@ -0,0 +1,95 @@
+mod common;
+
+use claims::{assert_err, assert_ok};
+use common::{names, parse};
+use insta::assert_snapshot;
+use seeyou_cup::Distance;
+
+const INPUT: &str = r#"name,code,country,lat,lon,elev,style
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5
+"Bled","BLED",SI,4622.100N,01406.820E,501m,1
+-----Related Tasks-----
+"Mixed","Lesce","Lesce","Bled","Bled Lake","Radovljica","Kranj","Lesce","Lesce"
+Point=2,"Bled","BLED",SI,4622.102N,01406.822E,501m,1
+Point=3,"Bled Lake","BLK",SI,4622.101N,01406.821E,501m,1
+Point=4,"Radovljica","RADO",SI,4620.800N,01410.500E,490m,1
+Point=5,"Kranj","KRAN",SI,4614.000N,01421.000E,386m,1
+"Second","Lesce","Lesce","Kranj","Lesce","Lesce"
+Point=2,"Kranj","KRAN",SI,4614.000N,01421.000E,386m,1
+"Far","Lesce","Lesce","Bled","Lesce","Lesce"
+Point=2,"Bled","BLD2",SI,4630.000N,01420.000E,700m,1
+"#;
+
+#[test]
+fn test_promote_inline_points() {
+    let mut cup = parse(INPUT);
+    let added = cup.promote_inline_points(&Distance::Meters(50.0));
+
+    assert_eq!(added, ["Radovljica", "Kranj", "Bled (2)"]);
+    assert_eq!(
+        names(&cup),
+        ["Lesce", "Bled", "Radovljica", "Kranj", "Bled (2)"]
+    );
+    assert!(cup.tasks.iter().all(|task| task.points.is_empty()));
+
+    // Points close to an existing waypoint reuse it, whatever their name
+    assert_eq!(
+        cup.tasks[0].waypoint_names,
+        [
+            "Lesce",
+            "Lesce",
+            "Bled",
+            "Bled",
+            "Radovljica",
+            "Kranj",
+            "Lesce",
+            "Lesce"
+        ]
+    );
+    assert_eq!(cup.tasks[1].waypoint_names[2], "Kranj");
+    assert_eq!(cup.tasks[2].waypoint_names[2], "Bled (2)");
+    assert_eq!(cup.waypoints[4].code, "BLD2");
+
+    // The promoted tasks resolve to the same positions
+    let original = parse(INPUT);
+    for (task, original_task) in cup.tasks.iter().zip(&original.tasks) {
+        let points = assert_ok!(task.resolve(&cup.waypoints));
+        let original_points = assert_ok!(original_task.resolve(&original.waypoints));
+        for (point, original_point) in points.iter().zip(&original_points) {
+            let (a, b) = (point.waypoint, original_point.waypoint);
+            assert!((a.latitude - b.latitude).abs() < 1e-3, "{}", a.name);
+            assert!((a.longitude - b.longitude).abs() < 1e-3, "{}", a.name);
+        }
+    }
+}
+
+#[test]
+fn test_inline_task_points() {
+    let mut cup = parse(INPUT);
+    cup.tasks[0].points.clear();
+    cup.tasks[0].waypoint_names[3] = "Bled".to_string();
+    cup.tasks[0].waypoint_names.drain(4..6);
+
+    assert_ok!(cup.inline_task_points());
+    let task = &cup.tasks[0];
+    let indices = task.points.iter().map(|(index, _)| *index);
+    assert_eq!(indices.collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+    assert_eq!(task.points[2].1, cup.waypoints[1]);
+
+    // Existing inline points are kept
+    assert_eq!(cup.tasks[2].points[2].1.code, "BLD2");
+
+    // The task no longer needs the waypoint list
+    let task = cup.tasks[0].clone();
+    cup.waypoints.clear();
+    assert_ok!(task.resolve(&cup.waypoints));
+}
+
+#[test]
+fn test_inline_unknown_waypoint() {
+    let mut cup = parse(INPUT);
+    cup.tasks[1].points.clear();
+
+    let error = assert_err!(cup.inline_task_points());
+    assert_snapshot!(error, @"Unknown waypoint: 'Kranj'");
+}
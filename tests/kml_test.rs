@@ -0,0 +1,177 @@
+mod common;
+
+use claims::{assert_err, assert_ok};
+use common::{is_counterclockwise, parse};
+use insta::assert_snapshot;
+
+const INPUT: &str = r#"name,code,country,lat,lon,elev,style,rwdir,rwlen,rwwidth,freq,desc,userdata,pics
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5,130,1140m,30m,123.500,"Grass & asphalt",,"lesce.jpg"
+"Bohinj","BOH",SI,4617.000N,01352.000E,520m,7,,,,,,,
+"Kranj","KRAN",SI,4614.000N,01421.000E,386m,1,,,,,,,
+-----Related Tasks-----
+"Day 1","Lesce","Lesce","Bohinj","Kranj","Lesce","Lesce"
+ObsZone=0,Style=2,R1=5000m,A1=90,Line=True
+ObsZone=1,Style=1,R1=10000m,A1=45
+ObsZone=2,Style=1,R1=500m,A1=180
+"#;
+
+#[test]
+fn test_write_kml_waypoints() {
+    let mut cup = parse(INPUT);
+    cup.waypoints.truncate(2);
+    cup.tasks.clear();
+
+    let kml = assert_ok!(cup.to_kml_string());
+    assert_snapshot!(kml, @r#"
+    <?xml version="1.0" encoding="UTF-8"?>
+    <kml xmlns="http://www.opengis.net/kml/2.2">
+      <Document>
+        <Style id="style-5">
+          <IconStyle>
+            <color>ff00ff00</color>
+            <Icon>
+              <href>http://maps.google.com/mapfiles/kml/shapes/airports.png</href>
+            </Icon>
+            <hotSpot x="0.5" y="0.5" xunits="fraction" yunits="fraction"/>
+          </IconStyle>
+        </Style>
+        <Style id="style-7">
+          <IconStyle>
+            <Icon>
+              <href>http://maps.google.com/mapfiles/kml/shapes/triangle.png</href>
+            </Icon>
+            <hotSpot x="0.5" y="0.5" xunits="fraction" yunits="fraction"/>
+          </IconStyle>
+        </Style>
+        <Style id="task">
+          <LineStyle>
+            <color>ff0000ff</color>
+            <width>2</width>
+          </LineStyle>
+        </Style>
+        <Style id="zone">
+          <LineStyle>
+            <color>ff00ffff</color>
+            <width>2</width>
+          </LineStyle>
+          <PolyStyle>
+            <color>4000ffff</color>
+          </PolyStyle>
+        </Style>
+        <Folder>
+          <name>Waypoints</name>
+          <Placemark>
+            <name>Lesce</name>
+            <description>&lt;b&gt;Code:&lt;/b&gt; LJBL&lt;br/&gt;&lt;b&gt;Country:&lt;/b&gt; SI&lt;br/&gt;&lt;b&gt;Type:&lt;/b&gt; Airfield with solid surface runway&lt;br/&gt;&lt;b&gt;Elevation:&lt;/b&gt; 504m&lt;br/&gt;&lt;b&gt;Runway:&lt;/b&gt; 130° 1140m × 30m&lt;br/&gt;&lt;b&gt;Frequency:&lt;/b&gt; 123.500&lt;br/&gt;&lt;b&gt;Description:&lt;/b&gt; Grass &amp;amp; asphalt&lt;br/&gt;&lt;img src=&quot;pics/lesce.jpg&quot; width=&quot;400&quot;/&gt;</description>
+            <styleUrl>#style-5</styleUrl>
+            <Point>
+              <coordinates>14.174500,46.356333,504</coordinates>
+            </Point>
+          </Placemark>
+          <Placemark>
+            <name>Bohinj</name>
+            <description>&lt;b&gt;Code:&lt;/b&gt; BOH&lt;br/&gt;&lt;b&gt;Country:&lt;/b&gt; SI&lt;br/&gt;&lt;b&gt;Type:&lt;/b&gt; Mountain Top&lt;br/&gt;&lt;b&gt;Elevation:&lt;/b&gt; 520m</description>
+            <styleUrl>#style-7</styleUrl>
+            <Point>
+              <coordinates>13.866667,46.283333,520</coordinates>
+            </Point>
+          </Placemark>
+        </Folder>
+      </Document>
+    </kml>
+    "#);
+}
+
+#[test]
+fn test_write_kml_task() {
+    let cup = parse(INPUT);
+    let kml = assert_ok!(cup.to_kml_string());
+
+    let tasks = &kml[kml.find("<name>Tasks</name>").unwrap()..];
+    let coordinates = |kml: &str| {
+        let start = kml.find("<coordinates>").unwrap() + "<coordinates>".len();
+        let end = kml.find("</coordinates>").unwrap();
+        kml[start..end]
+            .split(' ')
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    // The course goes from start to finish
+    assert_eq!(
+        coordinates(tasks),
+        [
+            "14.174500,46.356333",
+            "13.866667,46.283333",
+            "14.350000,46.233333",
+            "14.174500,46.356333"
+        ]
+    );
+
+    // Start line, FAI sector and cylinder, but no zone for the finish
+    let zones = tasks.matches("<styleUrl>#zone</styleUrl>").count();
+    assert_eq!(zones, 3);
+    assert_eq!(tasks.matches("<Polygon>").count(), 2);
+    assert_eq!(tasks.matches("<LineString>").count(), 2);
+
+    // Zone outlines are closed, counterclockwise rings
+    for ring in tasks.split("<LinearRing>").skip(1) {
+        let points = coordinates(ring);
+        assert!(points.len() > 10);
+        assert_eq!(points.first(), points.last());
+
+        let positions = points.iter().map(|point| {
+            let (lon, lat) = point.split_once(',').unwrap();
+            (lon.parse().unwrap(), lat.parse().unwrap())
+        });
+        assert!(is_counterclockwise(&positions.collect::<Vec<_>>()));
+    }
+}
+
+#[test]
+fn test_write_kml_zone_without_radius() {
+    let mut cup = parse(INPUT);
+    cup.tasks[0].observation_zones[2].r1 = None;
+    let kml = assert_ok!(cup.to_kml_string());
+
+    // The cylinder of Kranj has no outline and is left out
+    let zones = kml.matches("<styleUrl>#zone</styleUrl>").count();
+    assert_eq!(zones, 2);
+    assert_eq!(kml.matches("<Polygon>").count(), 1);
+}
+
+#[test]
+fn test_write_kml_unknown_waypoint() {
+    let mut cup = parse(INPUT);
+    cup.tasks[0].waypoint_names[2] = "Unknown".to_string();
+
+    let error = assert_err!(cup.to_kml_string());
+    assert_snapshot!(error, @"Unknown waypoint: 'Unknown'");
+}
+
+#[cfg(feature = "cupx")]
+#[test]
+fn test_write_kmz() {
+    use seeyou_cup::CupxFile;
+    use std::io::{Cursor, Read};
+    use zip::ZipArchive;
+
+    let mut cupx = CupxFile {
+        cup: parse(INPUT),
+        ..CupxFile::default()
+    };
+    cupx.pictures
+        .insert("lesce.jpg".to_string(), b"lesce".to_vec());
+
+    let mut output = Cursor::new(Vec::new());
+    assert_ok!(cupx.to_kmz_writer(&mut output));
+
+    let mut archive = assert_ok!(ZipArchive::new(output));
+    let names = archive.file_names().collect::<Vec<_>>();
+    assert_eq!(names, ["doc.kml", "pics/lesce.jpg"]);
+
+    let mut kml = String::new();
+    assert_ok!(assert_ok!(archive.by_name("doc.kml")).read_to_string(&mut kml));
+    assert_eq!(kml, assert_ok!(cupx.cup.to_kml_string()));
+    assert!(kml.contains("&lt;img src=&quot;pics/lesce.jpg&quot;"));
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use claims::{assert_none, assert_ok, assert_some};
+use common::parse;
+use seeyou_cup::{CupFile, DuplicateNames};
+
+const INPUT: &str = r#"name,code,country,lat,lon,elev,style
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5
+"Bled","BLED",SI,4622.100N,01406.820E,501m,1
+"Bled","BLED2",SI,4623.000N,01407.000E,480m,3
+"Radovljica","",SI,4620.800N,01410.500E,490m,1
+"Bled","",SI,4624.000N,01408.000E,470m,3
+"Kranj","KRAN",SI,4614.000N,01421.000E,386m,1
+"Kranj","",SI,4614.500N,01421.500E,390m,1
+"#;
+
+#[test]
+fn test_lookup_by_name() {
+    let cup = parse(INPUT);
+    let index = cup.index();
+
+    let waypoint = assert_some!(index.by_name("Radovljica"));
+    assert_eq!(waypoint.name, "Radovljica");
+    assert_eq!(index.position("Kranj"), Some(5));
+
+    // The first of several waypoints with the same name is returned
+    let waypoint = assert_some!(index.by_name("Bled"));
+    assert_eq!(waypoint.code, "BLED");
+
+    assert_none!(index.by_name("bled"));
+    assert_none!(index.by_name("Bohinj"));
+    let waypoint = assert_some!(index.by_name_ignore_case("RADOVLJICA"));
+    assert_eq!(waypoint.name, "Radovljica");
+}
+
+#[test]
+fn test_lookup_by_code() {
+    let cup = parse(INPUT);
+    let index = cup.index();
+
+    let waypoint = assert_some!(index.by_code("BLED2"));
+    assert_eq!(waypoint.elevation.to_meters(), 480.0);
+    assert_none!(index.by_code("ljbl"));
+    assert_none!(index.by_code(""));
+
+    let waypoint = assert_some!(index.by_code_ignore_case("ljbl"));
+    assert_eq!(waypoint.name, "Lesce");
+}
+
+#[test]
+fn test_duplicate_names() {
+    let cup = parse(INPUT);
+    assert_eq!(
+        cup.index().duplicates(),
+        [
+            DuplicateNames {
+                name: "Bled".to_string(),
+                indices: vec![1, 2, 4],
+            },
+            DuplicateNames {
+                name: "Kranj".to_string(),
+                indices: vec![5, 6],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_fixture_lookup() {
+    let (cup, _) = assert_ok!(CupFile::from_path("tests/fixtures/EC25.cup"));
+    let index = cup.index();
+
+    assert!(index.duplicates().is_empty());
+    for (position, waypoint) in cup.waypoints.iter().enumerate() {
+        assert_eq!(index.position(&waypoint.name), Some(position));
+    }
+    let waypoint = assert_some!(index.by_code("002ACBRA"));
+    assert_eq!(waypoint.name, "002 Aachen Brand Abfahrt");
+}
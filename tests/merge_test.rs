@@ -0,0 +1,107 @@
+mod common;
+
+use claims::assert_ok;
+use common::{names, parse};
+use insta::assert_snapshot;
+use seeyou_cup::{Distance, MatchBy, MergeOptions, MergeSide, Resolution};
+
+const NATIONAL: &str = r#"name,code,country,lat,lon,elev,style
+"Lesce","LJBL",SI,4621.380N,01410.470E,504m,5
+"Bled","BLED",SI,4622.100N,01406.820E,501m,1
+-----Related Tasks-----
+"Local","Lesce","Lesce","Bled","Lesce","Lesce"
+"#;
+
+const CLUB: &str = r#"name,code,country,lat,lon,elev,style,rwdir,rwlen,freq,desc
+"Lesce Bled","LJBL",SI,4621.380N,01410.470E,504m,5,130,1140m,123.500,"Home field"
+"Bled","BLD2",SI,4623.000N,01407.000E,480m,3,,,,"Outlanding near the lake"
+"Radovljica","RADO",SI,4620.800N,01410.500E,490m,1,,,,
+-----Related Tasks-----
+"Club","Lesce Bled","Lesce Bled","Bled","Radovljica","Lesce Bled","Lesce Bled"
+"#;
+
+#[test]
+fn test_merge_by_code_keep_left() {
+    let mut cup = parse(NATIONAL);
+    let options = MergeOptions {
+        matching: vec![MatchBy::Code],
+        resolution: Resolution::KeepLeft,
+    };
+
+    let report = cup.merge(parse(CLUB), &options);
+    assert_eq!(names(&cup), ["Lesce", "Bled", "Bled (2)", "Radovljica"]);
+    assert_eq!(report.matched[0].kept, MergeSide::Left);
+
+    // The club task now refers to the kept and renamed waypoints
+    assert_eq!(
+        cup.tasks[1].waypoint_names,
+        ["Lesce", "Lesce", "Bled (2)", "Radovljica", "Lesce", "Lesce"]
+    );
+    assert_snapshot!(report, @r"
+    Added 'Bled (2)'
+    Added 'Radovljica'
+    Matched 'Lesce' with 'Lesce Bled', kept left
+    Renamed 'Bled' to 'Bled (2)'
+    Tasks added: 1
+    ");
+}
+
+#[test]
+fn test_merge_prefer_richer() {
+    let mut cup = parse(NATIONAL);
+    let options = MergeOptions {
+        matching: vec![MatchBy::Code, MatchBy::Distance(Distance::Meters(100.0))],
+        resolution: Resolution::PreferRicher,
+    };
+
+    let report = cup.merge(parse(CLUB), &options);
+    assert_eq!(
+        names(&cup),
+        ["Lesce Bled", "Bled", "Bled (2)", "Radovljica"]
+    );
+    assert_eq!(cup.waypoints[0].frequency, "123.500");
+    assert_eq!(report.matched[0].kept, MergeSide::Right);
+
+    // The national task follows the renamed waypoint
+    assert_eq!(
+        cup.tasks[0].waypoint_names,
+        [
+            "Lesce Bled",
+            "Lesce Bled",
+            "Bled",
+            "Lesce Bled",
+            "Lesce Bled"
+        ]
+    );
+    assert_eq!(
+        cup.tasks[1].waypoint_names,
+        [
+            "Lesce Bled",
+            "Lesce Bled",
+            "Bled (2)",
+            "Radovljica",
+            "Lesce Bled",
+            "Lesce Bled"
+        ]
+    );
+    assert!(cup.validate().is_empty());
+}
+
+#[test]
+fn test_merge_by_name_keep_right() {
+    let mut cup = parse(NATIONAL);
+    let options = MergeOptions {
+        matching: vec![MatchBy::Name],
+        resolution: Resolution::KeepRight,
+    };
+
+    let report = cup.merge(parse(CLUB), &options);
+    assert_eq!(names(&cup), ["Lesce", "Bled", "Lesce Bled", "Radovljica"]);
+    assert_eq!(cup.waypoints[1].code, "BLD2");
+    assert_eq!(report.renamed, []);
+    assert_eq!(report.tasks_added, 1);
+
+    // The merged file contains the optional columns of both files
+    let output = assert_ok!(cup.to_string());
+    assert!(output.starts_with("name,code,country,lat,lon,elev,style,rwdir,rwlen,freq,desc\n"));
+}
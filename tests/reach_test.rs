@@ -0,0 +1,86 @@
+use claims::assert_ok;
+use seeyou_cup::{Coordinate, CupFile, Elevation, GlideParameters, Wind};
+
+const LANDABLES: &str = r#"name,code,country,lat,lon,elev,style
+"Near","N",XX,0000.000N,00006.000E,0m,4
+"Hill","H",XX,0000.000N,00003.000E,500m,3
+"Far","F",XX,0000.000N,00015.000E,0m,5
+"Too far","T",XX,0000.000N,00018.000E,0m,2
+"West","W",XX,0000.000N,00015.000W,0m,2
+"Tower","M",XX,0000.000N,00001.000E,0m,8
+"#;
+
+fn glide(wind: Option<Wind>) -> GlideParameters {
+    GlideParameters {
+        position: assert_ok!(Coordinate::new(0.0, 0.0)),
+        altitude: Elevation::Meters(1000.0),
+        glide_ratio: 40.0,
+        airspeed: 100.0,
+        safety_altitude: Elevation::Meters(200.0),
+        wind,
+    }
+}
+
+fn arrivals(cup: &CupFile, glide: &GlideParameters) -> Vec<(String, f64)> {
+    cup.reachable_landables(glide)
+        .into_iter()
+        .map(|landable| {
+            let height = landable.arrival_height.to_meters().round();
+            (landable.waypoint.name.clone(), height)
+        })
+        .collect()
+}
+
+#[test]
+fn test_reachable_in_still_air() {
+    let (cup, _) = assert_ok!(CupFile::from_str(LANDABLES));
+
+    assert_eq!(
+        arrivals(&cup, &glide(None)),
+        [
+            ("Near".to_string(), 722.0),
+            ("Hill".to_string(), 361.0),
+            ("Far".to_string(), 304.0),
+            ("West".to_string(), 304.0),
+        ]
+    );
+}
+
+#[test]
+fn test_reachable_with_wind() {
+    let (cup, _) = assert_ok!(CupFile::from_str(LANDABLES));
+
+    // Headwind to the east, tailwind to the west
+    let wind = Wind {
+        direction: 90.0,
+        speed: 20.0,
+    };
+    assert_eq!(
+        arrivals(&cup, &glide(Some(wind))),
+        [
+            ("Near".to_string(), 652.0),
+            ("West".to_string(), 420.0),
+            ("Hill".to_string(), 326.0),
+        ]
+    );
+}
+
+#[test]
+fn test_nothing_reachable() {
+    let (cup, _) = assert_ok!(CupFile::from_str(LANDABLES));
+
+    let mut low = glide(None);
+    low.altitude = Elevation::Meters(150.0);
+    assert_eq!(arrivals(&cup, &low), []);
+
+    let mut storm = glide(Some(Wind {
+        direction: 270.0,
+        speed: 150.0,
+    }));
+    storm.altitude = Elevation::Meters(3000.0);
+    let names = arrivals(&cup, &storm)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    assert!(!names.contains(&"West".to_string()));
+}
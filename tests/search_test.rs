@@ -0,0 +1,96 @@
+use claims::assert_ok;
+use insta::assert_snapshot;
+use seeyou_cup::{Coordinate, CupFile, SearchOptions};
+
+fn ec25() -> CupFile {
+    let (cup, _) = assert_ok!(CupFile::from_path("tests/fixtures/EC25.cup"));
+    cup
+}
+
+fn hotzenwald() -> CupFile {
+    let path = "tests/fixtures/2018_Hotzenwaldwettbewerb_V3.cup";
+    let (cup, _) = assert_ok!(CupFile::from_path(path));
+    cup
+}
+
+fn search(cup: &CupFile, query: &str, options: &SearchOptions) -> String {
+    cup.search(query, options)
+        .iter()
+        .map(|result| format!("{:.2} {}", result.score, result.waypoint.name))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_search_abbreviated_words() {
+    let cup = ec25();
+    let options = SearchOptions {
+        limit: 3,
+        ..SearchOptions::default()
+    };
+
+    assert_snapshot!(search(&cup, "aachen brnd", &options), @"0.92 002 Aachen Brand Abfahrt");
+}
+
+#[test]
+fn test_search_ignores_numeric_prefix() {
+    let cup = hotzenwald();
+    let options = SearchOptions {
+        limit: 3,
+        ..SearchOptions::default()
+    };
+
+    assert_snapshot!(search(&cup, "hotzn", &options), @r"
+    0.64 000_Huetten Hotz
+    0.64 999_Ziel Hotz
+    ");
+}
+
+#[test]
+fn test_search_folds_diacritics() {
+    let cup = ec25();
+    let options = SearchOptions {
+        limit: 1,
+        ..SearchOptions::default()
+    };
+
+    for query in ["Wuerselen", "Würselen", "wurselen", "wuerslen"] {
+        let results = cup.search(query, &options);
+        assert_eq!(results.len(), 1, "{query}");
+        assert_eq!(results[0].waypoint.name, "217 Wuerselen", "{query}");
+    }
+}
+
+#[test]
+fn test_search_by_code() {
+    let cup = ec25();
+    let results = cup.search("002acbra", &SearchOptions::default());
+    assert_eq!(results[0].waypoint.name, "002 Aachen Brand Abfahrt");
+    assert_eq!(results[0].score, 1.0);
+}
+
+#[test]
+fn test_search_weighted_by_distance() {
+    let cup = ec25();
+    let options = SearchOptions {
+        limit: 3,
+        reference: Some(assert_ok!(Coordinate::new(50.8, 6.2))),
+        ..SearchOptions::default()
+    };
+
+    let results = cup.search("aachen", &options);
+    assert_eq!(results.len(), 3);
+    assert!(results.iter().all(|result| result.distance.is_some()));
+    assert_snapshot!(search(&cup, "aachen", &options), @r"
+    0.98 005 Aachen Pumpstation
+    0.98 001 Aachen AB Kreuz
+    0.97 004 Aachen Merzbrueck
+    ");
+}
+
+#[test]
+fn test_search_without_match() {
+    let cup = ec25();
+    assert!(cup.search("xyzzy", &SearchOptions::default()).is_empty());
+    assert!(cup.search("  ", &SearchOptions::default()).is_empty());
+}
@@ -0,0 +1,155 @@
+use claims::assert_ok;
+use proptest::prelude::*;
+use seeyou_cup::{
+    Coordinate, CupFile, Distance, Elevation, SpatialIndex, StyleFilter, Waypoint, WaypointStyle,
+};
+
+fn waypoint(name: &str, latitude: f64, longitude: f64, style: WaypointStyle) -> Waypoint {
+    Waypoint {
+        name: name.to_string(),
+        code: String::new(),
+        country: String::new(),
+        latitude,
+        longitude,
+        elevation: Elevation::Meters(0.0),
+        style,
+        runway_direction: None,
+        runway_length: None,
+        runway_width: None,
+        frequency: String::new(),
+        description: String::new(),
+        userdata: String::new(),
+        pictures: vec![],
+    }
+}
+
+fn coordinate(latitude: f64, longitude: f64) -> Coordinate {
+    assert_ok!(Coordinate::new(latitude, longitude))
+}
+
+fn waypoints() -> Vec<Waypoint> {
+    vec![
+        waypoint("Lesce", 46.3563, 14.1745, WaypointStyle::SolidAirfield),
+        waypoint("Bled", 46.3683, 14.1136, WaypointStyle::Waypoint),
+        waypoint("Field", 46.30, 14.25, WaypointStyle::Outlanding),
+        waypoint("Ljubljana", 46.2237, 14.4576, WaypointStyle::SolidAirfield),
+        waypoint("Triglav", 46.3783, 13.8367, WaypointStyle::MountainTop),
+        waypoint("Udbina", 44.5578, 15.7744, WaypointStyle::GrassAirfield),
+        waypoint("Fiji", -17.0, 179.9, WaypointStyle::Waypoint),
+        waypoint("Samoa", -17.0, -179.9, WaypointStyle::Waypoint),
+    ]
+}
+
+fn names(neighbors: &[seeyou_cup::Neighbor<'_>]) -> Vec<String> {
+    neighbors.iter().map(|n| n.waypoint.name.clone()).collect()
+}
+
+#[test]
+fn test_nearest() {
+    let waypoints = waypoints();
+    let index = SpatialIndex::new(&waypoints);
+    let position = coordinate(46.36, 14.17);
+
+    let nearest = index.nearest(&position, 3, &StyleFilter::All);
+    assert_eq!(names(&nearest), ["Lesce", "Bled", "Field"]);
+    assert_eq!(nearest[0].index, 0);
+    assert!(nearest[0].distance.to_meters() < 1000.0);
+
+    let landables = index.nearest(&position, 10, &StyleFilter::Landables);
+    assert_eq!(names(&landables), ["Lesce", "Field", "Ljubljana", "Udbina"]);
+}
+
+#[test]
+fn test_within_radius() {
+    let waypoints = waypoints();
+    let index = SpatialIndex::new(&waypoints);
+    let position = coordinate(46.36, 14.17);
+
+    let nearby = index.within_radius(&position, &Distance::Kilometers(30.0), &StyleFilter::All);
+    assert_eq!(
+        names(&nearby),
+        ["Lesce", "Bled", "Field", "Triglav", "Ljubljana"]
+    );
+
+    let filter = StyleFilter::Only(vec![WaypointStyle::MountainTop]);
+    let peaks = index.within_radius(&position, &Distance::Kilometers(30.0), &filter);
+    assert_eq!(names(&peaks), ["Triglav"]);
+}
+
+#[test]
+fn test_within_bounds() {
+    let waypoints = waypoints();
+    let index = SpatialIndex::new(&waypoints);
+
+    let found = index.within_bounds(
+        &coordinate(46.0, 14.0),
+        &coordinate(47.0, 15.0),
+        &StyleFilter::Landables,
+    );
+    let found = found.iter().map(|w| w.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(found, ["Lesce", "Field", "Ljubljana"]);
+
+    // The box crosses the antimeridian
+    let found = index.within_bounds(
+        &coordinate(-18.0, 179.0),
+        &coordinate(-16.0, -179.0),
+        &StyleFilter::All,
+    );
+    let found = found.iter().map(|w| w.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(found, ["Fiji", "Samoa"]);
+}
+
+#[test]
+fn test_antimeridian_neighbors() {
+    let waypoints = waypoints();
+    let index = SpatialIndex::new(&waypoints);
+
+    let nearest = index.nearest(&coordinate(-17.0, 179.95), 2, &StyleFilter::All);
+    assert_eq!(names(&nearest), ["Fiji", "Samoa"]);
+}
+
+#[test]
+fn test_cup_file_index() {
+    let input = r#"name,code,country,lat,lon,elev,style
+"Home","H",XX,0000.000N,00000.000E,0m,4
+"East","E",XX,0000.000N,00100.000E,0m,1
+"#;
+    let (cup, _) = assert_ok!(CupFile::from_str(input));
+
+    let index = cup.spatial_index();
+    let nearest = index.nearest(&coordinate(0.0, 0.9), 1, &StyleFilter::All);
+    assert_eq!(names(&nearest), ["East"]);
+}
+
+proptest! {
+    #[test]
+    fn test_radius_matches_linear_scan(
+        points in prop::collection::vec((-89.0..89.0f64, -180.0..180.0f64), 1..50),
+        center in (-89.0..89.0f64, -180.0..180.0f64),
+        radius in 1.0..3_000_000.0f64,
+    ) {
+        let waypoints = points
+            .iter()
+            .map(|&(lat, lon)| waypoint("", lat, lon, WaypointStyle::Waypoint))
+            .collect::<Vec<_>>();
+        let index = SpatialIndex::new(&waypoints);
+        let center = coordinate(center.0, center.1);
+
+        let mut expected = waypoints
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| {
+                let position = coordinate(w.latitude, w.longitude);
+                center.distance_to(&position).to_meters() <= radius
+            })
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+
+        let found = index.within_radius(&center, &Distance::Meters(radius), &StyleFilter::All);
+        let mut found = found.iter().map(|n| n.index).collect::<Vec<_>>();
+        found.sort_unstable();
+
+        prop_assert_eq!(found, expected);
+    }
+}
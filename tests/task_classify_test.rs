@@ -0,0 +1,121 @@
+use claims::{assert_none, assert_ok, assert_some};
+use seeyou_cup::{CupFile, TaskKind};
+use std::path::Path;
+
+const WAYPOINTS: &str = r#"name,code,country,lat,lon,elev,style
+"Home","H",XX,0000.000N,00000.000E,0m,4
+"East","E",XX,0000.000N,00100.000E,0m,1
+"North","N",XX,0100.000N,00000.000E,0m,1
+"Far East","F",XX,0000.000N,00300.000E,0m,1
+"North East","NE",XX,0100.000N,00100.000E,0m,1
+"#;
+
+fn parse(tasks: &str) -> CupFile {
+    let input = format!("{WAYPOINTS}-----Related Tasks-----\n{tasks}");
+    let (cup, _) = assert_ok!(CupFile::from_str(&input));
+    cup
+}
+
+fn percent(part: f64, total: f64) -> f64 {
+    (part / total * 1000.0).round() / 10.0
+}
+
+#[test]
+fn test_classify_fixture_triangle() {
+    let path = Path::new("tests/fixtures/709-km-Dreieck-DMSt-Aachen-Stolberg-TV.cup");
+    let (cup, _) = assert_ok!(CupFile::from_path(path));
+
+    let classification = assert_ok!(cup.tasks[0].classify(&cup.waypoints));
+    assert_eq!(classification.kind, TaskKind::Racing);
+
+    let triangle = assert_some!(classification.triangle);
+    let total = triangle.total.to_meters();
+    let legs = triangle.legs.map(|leg| percent(leg.to_meters(), total));
+    assert_eq!(legs, [31.8, 39.4, 28.8]);
+    assert!(triangle.fai);
+}
+
+#[test]
+fn test_classify_out_and_return() {
+    let cup = parse("\"O&R\",\"Home\",\"Home\",\"East\",\"Home\",\"Home\"\n");
+
+    let classification = assert_ok!(cup.tasks[0].classify(&cup.waypoints));
+    assert_eq!(classification.kind, TaskKind::OutAndReturn);
+    assert_none!(classification.triangle);
+}
+
+#[test]
+fn test_classify_triangles() {
+    let cup = parse(concat!(
+        "\"Right angle\",\"Home\",\"Home\",\"East\",\"North\",\"Home\",\"Home\"\n",
+        "\"Flat\",\"Home\",\"Home\",\"North\",\"Far East\",\"Home\",\"Home\"\n",
+    ));
+
+    let classification = assert_ok!(cup.tasks[0].classify(&cup.waypoints));
+    assert_eq!(classification.kind, TaskKind::Racing);
+    let triangle = assert_some!(classification.triangle);
+    assert_eq!(triangle.corners[1].longitude(), 1.0);
+    assert!(triangle.fai);
+
+    let classification = assert_ok!(cup.tasks[1].classify(&cup.waypoints));
+    let triangle = assert_some!(classification.triangle);
+    assert!(!triangle.fai);
+}
+
+#[test]
+fn test_classify_quadrilateral() {
+    let cup = parse(concat!(
+        "\"Square\",\"Home\",\"Home\",\"East\",\"North East\",\"North\",\"Home\",\"Home\"\n",
+        "\"On leg\",\"East\",\"East\",\"Home\",\"North\",\"Far East\",\"East\",\"East\"\n",
+    ));
+
+    // The start is far from the leg from North back to East
+    let classification = assert_ok!(cup.tasks[0].classify(&cup.waypoints));
+    assert_eq!(classification.kind, TaskKind::Racing);
+    assert_none!(classification.triangle);
+
+    // The start lies on the leg from Far East back to Home
+    let classification = assert_ok!(cup.tasks[1].classify(&cup.waypoints));
+    let triangle = assert_some!(classification.triangle);
+    assert_eq!(triangle.corners[0].longitude(), 0.0);
+    assert_eq!(triangle.corners[2].longitude(), 3.0);
+}
+
+#[test]
+fn test_classify_racing_goal() {
+    let cup = parse("\"Goal\",\"Home\",\"Home\",\"East\",\"Far East\",\"Far East\"\n");
+
+    let classification = assert_ok!(cup.tasks[0].classify(&cup.waypoints));
+    assert_eq!(classification.kind, TaskKind::Racing);
+    assert_none!(classification.triangle);
+}
+
+#[test]
+fn test_classify_assigned_area() {
+    let cup = parse(concat!(
+        "\"Timed\",\"Home\",\"Home\",\"East\",\"North\",\"Home\",\"Home\"\n",
+        "Options,TaskTime=03:00:00\n",
+        "\"Sectors\",\"Home\",\"Home\",\"East\",\"Home\",\"Home\"\n",
+        "ObsZone=1,Style=0,R1=20000m,A1=60,A12=90\n",
+    ));
+
+    for task in &cup.tasks {
+        let classification = assert_ok!(task.classify(&cup.waypoints));
+        assert_eq!(classification.kind, TaskKind::AssignedArea);
+        assert_none!(classification.triangle);
+    }
+}
+
+#[test]
+fn test_classify_free() {
+    let cup = parse(concat!(
+        "\"Random\",\"Home\",\"Home\",\"East\",\"North\",\"Home\",\"Home\"\n",
+        "Options,RandomOrder=True\n",
+        "\"Single\",\"Home\",\"Home\",\"Home\"\n",
+    ));
+
+    for task in &cup.tasks {
+        let classification = assert_ok!(task.classify(&cup.waypoints));
+        assert_eq!(classification.kind, TaskKind::Free);
+    }
+}
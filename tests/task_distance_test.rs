@@ -0,0 +1,168 @@
+use claims::{assert_err, assert_ok};
+use insta::assert_snapshot;
+use seeyou_cup::{CupFile, Task};
+use std::path::Path;
+
+const EQUATOR_TASK: &str = r#"name,code,country,lat,lon,elev,style
+"Home","H",XX,0000.000N,00000.000E,0m,4
+"East","E",XX,0000.000N,00100.000E,0m,1
+-----Related Tasks-----
+"Out and return","Home","Home","East","Home","Home"
+ObsZone=0,Style=2,R1=5000m,A1=180,Line=1
+ObsZone=1,Style=1,R1=10000m,A1=180
+ObsZone=2,Style=3,R1=1000m,A1=180
+"#;
+
+fn fixture() -> CupFile {
+    let path = Path::new("tests/fixtures/709-km-Dreieck-DMSt-Aachen-Stolberg-TV.cup");
+    let (cup, _) = assert_ok!(CupFile::from_path(path));
+    cup
+}
+
+fn km(meters: f64) -> f64 {
+    (meters / 10.0).round() / 100.0
+}
+
+#[test]
+fn test_resolve_task_points() {
+    let cup = fixture();
+    let task = &cup.tasks[0];
+
+    assert_eq!(task.course_names().len(), 5);
+    let points = assert_ok!(task.resolve(&cup.waypoints));
+    assert_eq!(points.len(), 5);
+    assert_eq!(points[0].index, 0);
+    assert_eq!(points[0].waypoint.name, "Aachen Stolberg TV");
+    assert_eq!(points[2].waypoint.name, "Helmstadter Berg");
+    assert_eq!(points[4].observation_zone.map(|oz| oz.index), Some(4));
+}
+
+#[test]
+fn test_resolve_inline_points() {
+    let input = r#"name,code,country,lat,lon,elev,style
+"Home","H",XX,0000.000N,00000.000E,0m,4
+-----Related Tasks-----
+"Inline","Home","Home","Somewhere","Home","Home"
+Point=2,"Somewhere","S",XX,0000.000N,00100.000E,0m,1
+"#;
+
+    let (cup, _) = assert_ok!(CupFile::from_str(input));
+    let task = &cup.tasks[0];
+    let points = assert_ok!(task.resolve(&cup.waypoints));
+    assert_eq!(points[1].waypoint.name, "Somewhere");
+    assert_eq!(points[1].waypoint.longitude, 1.0);
+
+    let distance = assert_ok!(task.nominal_distance(&cup.waypoints));
+    assert_eq!(km(distance.total.to_meters()), 222.64);
+}
+
+#[test]
+fn test_course_names_trailing_comma() {
+    // Task line from the example of the specification
+    let input = r#"name,code,country,lat,lon,elev,style
+"0LESCE","LJBL",SI,4621.379N,01410.467E,504.0m,5
+"0Start","START",SI,4620.000N,01412.000E,500.0m,1
+"750 Huje","HUJE",SI,4617.000N,01352.000E,800.0m,1
+"750 Brenner","BREN",AT,4700.000N,01130.000E,1370.0m,1
+"750 Gahns","GAHN",AT,4742.000N,01555.000E,1100.0m,1
+-----Related Tasks-----
+,"0LESCE","0Start","750 Huje","750 Brenner","750 Gahns","0Start","0LESCE",
+"Open end","0LESCE","0Start","750 Huje",""
+"#;
+
+    let (cup, _) = assert_ok!(CupFile::from_str(input));
+    let task = &cup.tasks[0];
+    assert_eq!(task.waypoint_names.len(), 7);
+    assert_eq!(
+        task.course_names(),
+        ["0Start", "750 Huje", "750 Brenner", "750 Gahns", "0Start"]
+    );
+    assert_eq!(assert_ok!(task.resolve(&cup.waypoints)).len(), 5);
+
+    // A quoted empty landing is kept, and written quoted again
+    let task = &cup.tasks[1];
+    assert_eq!(task.course_names(), ["0Start", "750 Huje"]);
+    let output = assert_ok!(cup.to_string());
+    assert!(output.contains("Open end,0LESCE,0Start,750 Huje,\"\"\n"));
+    let (roundtrip, _) = assert_ok!(CupFile::from_str(&output));
+    assert_eq!(roundtrip.tasks, cup.tasks);
+}
+
+#[test]
+fn test_resolve_unknown_waypoint() {
+    let task = Task {
+        description: None,
+        waypoint_names: vec!["".into(), "Nowhere".into(), "".into()],
+        options: None,
+        observation_zones: vec![],
+        points: vec![],
+        multiple_starts: vec![],
+    };
+
+    let err = assert_err!(task.nominal_distance(&[]));
+    assert_snapshot!(err, @"Unknown waypoint: 'Nowhere'");
+}
+
+#[test]
+fn test_nominal_distance() {
+    let cup = fixture();
+    let distance = assert_ok!(cup.tasks[0].nominal_distance(&cup.waypoints));
+
+    let legs = distance.legs.iter().map(|leg| km(leg.to_meters()));
+    assert_eq!(legs.collect::<Vec<_>>(), [182.16, 225.56, 279.8, 23.0]);
+    assert_eq!(km(distance.total.to_meters()), 710.53);
+    assert_eq!(distance.points.len(), 5);
+}
+
+#[test]
+fn test_optimized_distance_through_cylinders_and_lines() {
+    let (cup, _) = assert_ok!(CupFile::from_str(EQUATOR_TASK));
+    let task = &cup.tasks[0];
+
+    let nominal = assert_ok!(task.nominal_distance(&cup.waypoints));
+    let optimized = assert_ok!(task.optimized_distance(&cup.waypoints));
+
+    // The start line passes through the start point, the turnpoint cylinder
+    // shortens both legs and the finish cylinder shortens the last leg
+    let leg = nominal.legs[0].to_meters();
+    assert!((optimized.legs[0].to_meters() - (leg - 10_000.0)).abs() < 1.0);
+    assert!((optimized.legs[1].to_meters() - (leg - 11_000.0)).abs() < 1.0);
+    assert!((optimized.total.to_meters() - (2.0 * leg - 21_000.0)).abs() < 1.0);
+
+    assert!(optimized.points[0].longitude().abs() < 1e-6);
+    assert!((optimized.points[1].longitude() - 0.91017).abs() < 1e-4);
+}
+
+#[test]
+fn test_optimized_distance_through_fai_sectors() {
+    let cup = fixture();
+    let task = &cup.tasks[0];
+    let nominal = assert_ok!(task.nominal_distance(&cup.waypoints));
+    let optimized = assert_ok!(task.optimized_distance(&cup.waypoints));
+
+    // The sectors touch the course at the turnpoints, only the start and
+    // finish cylinders shorten the task
+    assert_eq!(optimized.points[1], nominal.points[1]);
+    assert_eq!(optimized.points[2], nominal.points[2]);
+    let shortening = nominal.total.to_meters() - optimized.total.to_meters();
+    assert!((shortening - 2000.0).abs() < 1.0);
+}
+
+#[test]
+fn test_distance_respects_wp_dis() {
+    let mut input = EQUATOR_TASK.to_string();
+    input.push_str("Options,WpDis=True\n");
+    let (cup, _) = assert_ok!(CupFile::from_str(&input));
+    let task = &cup.tasks[0];
+
+    let distance = assert_ok!(task.distance(&cup.waypoints));
+    assert_eq!(distance, assert_ok!(task.nominal_distance(&cup.waypoints)));
+
+    let mut task = task.clone();
+    task.options.as_mut().unwrap().wp_dis = Some(false);
+    let distance = assert_ok!(task.distance(&cup.waypoints));
+    assert_eq!(
+        distance,
+        assert_ok!(task.optimized_distance(&cup.waypoints))
+    );
+}
@@ -371,6 +371,19 @@ STARTS=Celovec,Hodos,Ratitovec,Jamnik
     assert_eq!(cup.tasks[0].multiple_starts[3], "Jamnik");
 }
 
+#[test]
+fn test_parse_quoted_starts_line() {
+    let input = r#"name,code,country,lat,lon,elev,style
+"Celovec","C",XX,5147.809N,00405.003W,500m,1
+-----Related Tasks-----
+,"Celovec"
+STARTS="Celovec","Hodos, North"
+"#;
+
+    let (cup, _) = assert_ok!(CupFile::from_str(input));
+    assert_eq!(cup.tasks[0].multiple_starts, ["Celovec", "Hodos, North"]);
+}
+
 #[test]
 fn test_multiple_starts_waypoints_defined() {
     let input = r#"name,code,country,lat,lon,elev,style
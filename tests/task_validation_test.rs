@@ -0,0 +1,145 @@
+use claims::assert_ok;
+use insta::assert_snapshot;
+use seeyou_cup::{
+    CupFile, Distance, DuplicateNames, FileDiagnostic, ObservationZone, Task, TaskIssue,
+    TaskOptions,
+};
+use std::path::Path;
+
+fn task(names: &[&str]) -> Task {
+    Task {
+        description: None,
+        waypoint_names: names.iter().map(|name| name.to_string()).collect(),
+        options: None,
+        observation_zones: vec![],
+        points: vec![],
+        multiple_starts: vec![],
+    }
+}
+
+#[test]
+fn test_valid_fixture() {
+    let path = Path::new("tests/fixtures/709-km-Dreieck-DMSt-Aachen-Stolberg-TV.cup");
+    let (cup, _) = assert_ok!(CupFile::from_path(path));
+
+    assert_eq!(cup.validate(), vec![]);
+}
+
+#[test]
+fn test_too_few_points() {
+    let task = task(&["Home", "Home", "Home"]);
+    assert_eq!(task.validate(), vec![TaskIssue::TooFewPoints { count: 1 }]);
+}
+
+#[test]
+fn test_invalid_options() {
+    let mut task = task(&["Home", "A", "B", "C", "Home"]);
+
+    task.options = Some(TaskOptions {
+        max_pts: Some(3),
+        before_pts: Some(2),
+        after_pts: Some(2),
+        ..TaskOptions::default()
+    });
+    assert_eq!(
+        task.validate(),
+        vec![TaskIssue::MandatoryPointsExceedMaximum {
+            before: 2,
+            after: 2,
+            max: 3
+        }]
+    );
+
+    task.options = Some(TaskOptions {
+        random_order: Some(true),
+        ..TaskOptions::default()
+    });
+    assert_eq!(
+        task.validate(),
+        vec![TaskIssue::RandomOrderWithoutMaxPoints]
+    );
+}
+
+#[test]
+fn test_invalid_zones() {
+    let mut task = task(&["Home", "A", "B", "Home"]);
+
+    let mut line = ObservationZone::line(0, Distance::Kilometers(10.0));
+    line.a1 = Some(45.0);
+    let mut sector = ObservationZone::fai_sector(1, Distance::Kilometers(20.0));
+    sector.r2 = Some(Distance::Meters(-500.0));
+    sector.a1 = Some(200.0);
+    let extra = ObservationZone::cylinder(2, Distance::Meters(500.0));
+    task.observation_zones = vec![line, sector, extra];
+
+    let issues = task.validate();
+    assert_eq!(
+        issues[0],
+        TaskIssue::LineWithAngle {
+            zone: 0,
+            angle: 45.0
+        }
+    );
+
+    let messages = issues.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_snapshot!(messages.join("\n"), @r"
+    ObsZone=0 is a line but has A1=45
+    ObsZone=1 has negative R2=-500m
+    ObsZone=1 has A1=200 (must be between 0 and 180)
+    ObsZone=2 does not refer to a task point
+    ");
+}
+
+#[test]
+fn test_line_with_default_angle() {
+    let mut task = task(&["Home", "A", "B", "Home"]);
+    task.observation_zones = vec![ObservationZone::line(0, Distance::Kilometers(10.0))];
+
+    assert_eq!(task.validate(), vec![]);
+}
+
+#[test]
+fn test_validate_file() {
+    let input = r#"name,code,country,lat,lon,elev,style
+"Home","H",XX,0000.000N,00000.000E,0m,4
+-----Related Tasks-----
+"Broken","Home","Home","Nowhere","Somewhere","Home"
+Point=3,"Somewhere","S",XX,0000.000N,00100.000E,0m,1
+,"Home","Home","Home"
+"#;
+
+    let (cup, _) = assert_ok!(CupFile::from_str(input));
+    let diagnostics = cup.validate();
+    assert!(matches!(&diagnostics[0], FileDiagnostic::Task(d) if d.task == 0));
+
+    let messages = diagnostics
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_snapshot!(messages.join("\n"), @r"
+    Task 'Broken': Unknown waypoint: 'Nowhere'
+    Task #2: Task needs at least 2 points, found 1
+    ");
+}
+
+#[test]
+fn test_validate_duplicate_names() {
+    let input = r#"name,code,country,lat,lon,elev,style
+"Home","H",XX,0000.000N,00000.000E,0m,4
+"East","E1",XX,0000.000N,00100.000E,0m,1
+"East","E2",XX,0000.000N,00200.000E,0m,1
+-----Related Tasks-----
+"Ambiguous","Home","Home","East","Home","Home"
+"#;
+
+    let (cup, _) = assert_ok!(CupFile::from_str(input));
+    let diagnostics = cup.validate();
+    assert_eq!(
+        diagnostics,
+        [FileDiagnostic::DuplicateName(DuplicateNames {
+            name: "East".to_string(),
+            indices: vec![1, 2],
+        })]
+    );
+    assert_snapshot!(diagnostics[0], @"Waypoint name used 2 times: 'East'");
+}
@@ -1,8 +1,8 @@
 use claims::{assert_ok, assert_some_eq};
 use insta::assert_snapshot;
 use seeyou_cup::{
-    CupFile, Distance, Elevation, Encoding, ObsZoneStyle, ObservationZone, RunwayDimension, Task,
-    TaskOptions, Waypoint, WaypointStyle,
+    Column, ColumnLayout, CupFile, Distance, Elevation, Encoding, ObsZoneStyle, ObservationZone,
+    RunwayDimension, Task, TaskOptions, Waypoint, WaypointStyle,
 };
 use std::io::Cursor;
 
@@ -554,3 +554,68 @@ fn test_comprehensive_roundtrip() {
     assert!((wp.latitude - 40.123456).abs() < 0.001);
     assert!((wp.longitude - (-74.987654)).abs() < 0.001);
 }
+
+#[test]
+fn test_preserve_column_order() {
+    let input = r#"lat,lon,elev,name,code,country,style,desc
+5147.809N,00405.003W,500m,Start,S,XX,2,First
+5149.000N,00407.000W,700m,Finish,F,XX,2,Last
+-----Related Tasks-----
+Task,Start,TP1,Finish
+Point=1,5148.000N,00406.000W,600m,TP1,T1,XX,1,Inline
+"#;
+
+    let (cup, _) = assert_ok!(CupFile::from_str(input));
+    let output = assert_ok!(cup.to_string());
+    assert_eq!(output, input);
+
+    let (parsed, _) = assert_ok!(CupFile::from_str(&output));
+    assert_eq!(parsed, cup);
+    assert_eq!(parsed.tasks[0].points[0].1.name, "TP1");
+}
+
+#[test]
+fn test_column_layout_appends_missing_columns_with_data() {
+    let input = r#"name,code,country,lat,lon,elev,style
+Start,S,XX,5147.809N,00405.003W,500m,2
+"#;
+
+    let (mut cup, _) = assert_ok!(CupFile::from_str(input));
+    cup.waypoints[0].frequency = "123.500".to_string();
+
+    let output = assert_ok!(cup.to_string());
+    assert_snapshot!(output, @r"
+    name,code,country,lat,lon,elev,style,freq
+    Start,S,XX,5147.809N,00405.003W,500m,2,123.500
+    ");
+}
+
+#[test]
+fn test_custom_column_layout() {
+    let mut cup_file = CupFile {
+        columns: ColumnLayout::new([Column::Code, Column::Name]),
+        ..CupFile::default()
+    };
+    cup_file.waypoints.push(Waypoint {
+        name: "Start".to_string(),
+        code: "S".to_string(),
+        country: "XX".to_string(),
+        latitude: 0.0,
+        longitude: 0.0,
+        elevation: Elevation::Meters(0.0),
+        style: WaypointStyle::Waypoint,
+        runway_direction: None,
+        runway_length: None,
+        runway_width: None,
+        frequency: String::new(),
+        description: String::new(),
+        userdata: String::new(),
+        pictures: vec![],
+    });
+
+    let output = assert_ok!(cup_file.to_string());
+    assert_snapshot!(output, @r"
+    code,name,country,lat,lon,elev,style
+    S,Start,XX,0000.000N,00000.000E,0m,1
+    ");
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use claims::{assert_none, assert_ok, assert_some};
+use common::is_counterclockwise;
+use seeyou_cup::{Coordinate, CupFile, Distance, ObsZoneStyle, ObservationZone};
+
+fn coordinate(latitude: f64, longitude: f64) -> Coordinate {
+    assert_ok!(Coordinate::new(latitude, longitude))
+}
+
+fn zone(style: ObsZoneStyle, r1: f64, a1: f64) -> ObservationZone {
+    ObservationZone {
+        index: 1,
+        style,
+        r1: Some(Distance::Meters(r1)),
+        a1: Some(a1),
+        r2: None,
+        a2: None,
+        a12: None,
+        line: None,
+    }
+}
+
+#[test]
+fn test_symmetrical_sector_orientation() {
+    // Coming from the south, leaving to the east: the sector opens to the north-west
+    let point = coordinate(0.0, 0.0);
+    let previous = coordinate(-1.0, 0.0);
+    let next = coordinate(0.0, 1.0);
+
+    let fai = zone(ObsZoneStyle::Symmetrical, 20_000.0, 45.0);
+    let geometry = fai.geometry(&point, Some(&previous), Some(&next), Some(&previous));
+    assert!((geometry.direction() - 315.0).abs() < 0.1);
+
+    assert!(geometry.contains(0.1, -0.1));
+    assert!(!geometry.contains(-0.1, 0.1));
+    assert!(!geometry.contains(0.1, 0.0001));
+    assert!(!geometry.contains(0.15, -0.15));
+}
+
+#[test]
+fn test_directional_styles() {
+    let point = coordinate(0.0, 0.0);
+    let previous = coordinate(-1.0, 0.0);
+    let next = coordinate(0.0, 1.0);
+    let start = coordinate(0.0, -1.0);
+
+    let direction = |style: ObsZoneStyle| {
+        let zone = zone(style, 1000.0, 45.0);
+        let geometry = zone.geometry(&point, Some(&previous), Some(&next), Some(&start));
+        geometry.direction().round()
+    };
+
+    assert_eq!(direction(ObsZoneStyle::ToNextPoint), 270.0);
+    assert_eq!(direction(ObsZoneStyle::ToPreviousPoint), 0.0);
+    assert_eq!(direction(ObsZoneStyle::ToStartPoint), 90.0);
+
+    let mut fixed = zone(ObsZoneStyle::Fixed, 1000.0, 45.0);
+    fixed.a12 = Some(123.0);
+    let geometry = fixed.geometry(&point, Some(&previous), Some(&next), None);
+    assert_eq!(geometry.direction(), 123.0);
+}
+
+#[test]
+fn test_keyhole_contains() {
+    let mut keyhole = zone(ObsZoneStyle::ToPreviousPoint, 10_000.0, 45.0);
+    keyhole.r2 = Some(Distance::Meters(500.0));
+    keyhole.a2 = Some(180.0);
+
+    let point = coordinate(0.0, 0.0);
+    let previous = coordinate(-1.0, 0.0);
+    let geometry = keyhole.geometry(&point, Some(&previous), None, None);
+
+    // Inside the sector, beyond the previous point
+    assert!(geometry.contains(0.08, 0.0));
+    // Inside the inner cylinder only
+    assert!(geometry.contains(-0.004, 0.0));
+    // Outside of both
+    assert!(!geometry.contains(-0.08, 0.0));
+}
+
+#[test]
+fn test_line_crossing() {
+    let mut start = zone(ObsZoneStyle::ToNextPoint, 5000.0, 180.0);
+    start.line = Some(true);
+
+    let point = coordinate(0.0, 0.0);
+    let next = coordinate(0.0, 1.0);
+    let geometry = start.geometry(&point, None, Some(&next), None);
+    assert!(geometry.is_line());
+
+    // Crossing the north-south line eastbound, 2 km north of the center
+    let from = coordinate(0.018, -0.01);
+    let to = coordinate(0.018, 0.01);
+    let crossing = assert_some!(geometry.line_crossing(&from, &to));
+    assert!(crossing.longitude().abs() < 1e-6);
+    assert!((crossing.latitude() - 0.018).abs() < 1e-6);
+    assert!(geometry.contains(from.latitude(), from.longitude()));
+    assert!(!geometry.contains(to.latitude(), to.longitude()));
+
+    // Passing north of the line end
+    let from = coordinate(0.05, -0.01);
+    let to = coordinate(0.05, 0.01);
+    assert_none!(geometry.line_crossing(&from, &to));
+
+    // Not reaching the line
+    let to = coordinate(0.018, -0.001);
+    assert_none!(geometry.line_crossing(&coordinate(0.018, -0.01), &to));
+}
+
+#[test]
+fn test_polygon() {
+    let point = coordinate(0.0, 0.0);
+    let next = coordinate(0.0, 1.0);
+
+    let cylinder = zone(ObsZoneStyle::Symmetrical, 500.0, 180.0);
+    let polygon = cylinder.geometry(&point, None, Some(&next), None).polygon();
+    assert_eq!(polygon.first(), polygon.last());
+    assert_eq!(polygon.len(), 181);
+    let ring = polygon.iter().map(|c| (c.longitude(), c.latitude()));
+    assert!(is_counterclockwise(&ring.collect::<Vec<_>>()));
+    for vertex in &polygon {
+        assert!((vertex.distance_to(&point).to_meters() - 500.0).abs() < 1e-3);
+    }
+
+    let sector = zone(ObsZoneStyle::ToNextPoint, 10_000.0, 45.0);
+    let polygon = sector.geometry(&point, None, Some(&next), None).polygon();
+    assert_eq!(polygon.first(), polygon.last());
+    assert!(polygon.contains(&point));
+    let ring = polygon.iter().map(|c| (c.longitude(), c.latitude()));
+    assert!(is_counterclockwise(&ring.collect::<Vec<_>>()));
+    assert!(polygon.iter().all(|vertex| vertex.longitude() <= 1e-9));
+
+    let mut line = zone(ObsZoneStyle::ToNextPoint, 5000.0, 180.0);
+    line.line = Some(true);
+    let polygon = line.geometry(&point, None, Some(&next), None).polygon();
+    assert_eq!(polygon.len(), 2);
+    assert!((polygon[0].distance_to(&polygon[1]).to_meters() - 10_000.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_task_zone_geometries() {
+    let path = "tests/fixtures/709-km-Dreieck-DMSt-Aachen-Stolberg-TV.cup";
+    let (cup, _) = assert_ok!(CupFile::from_path(path));
+    let geometries = assert_ok!(cup.tasks[0].zone_geometries(&cup.waypoints));
+    assert_eq!(geometries.len(), 5);
+
+    let wendepunkt = &cup.waypoints[1];
+    assert_eq!(geometries[1].center(), assert_ok!(wendepunkt.coordinate()));
+
+    // The FAI sector at the southern turnpoint of the triangle opens to the south-west
+    assert!((180.0..270.0).contains(&geometries[1].direction()));
+}
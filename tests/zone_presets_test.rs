@@ -0,0 +1,170 @@
+use claims::{assert_none, assert_ok, assert_some_eq};
+use seeyou_cup::{CupFile, Distance, ObsZoneStyle, ObservationZone, Task, ZonePreset};
+use std::path::Path;
+
+fn task_with_zones(zones: Vec<ObservationZone>) -> Task {
+    Task {
+        description: Some("Presets".to_string()),
+        waypoint_names: vec!["A".to_string(), "B".to_string(), "C".to_string()],
+        options: None,
+        observation_zones: zones,
+        points: vec![],
+        multiple_starts: vec![],
+    }
+}
+
+#[test]
+fn test_preset_fields() {
+    let fai = ObservationZone::fai_sector(1, Distance::Kilometers(20.0));
+    assert_eq!(fai.style, ObsZoneStyle::Symmetrical);
+    assert_eq!(fai.r1, Some(Distance::Kilometers(20.0)));
+    assert_eq!(fai.a1, Some(45.0));
+
+    let start = ObservationZone::line(0, Distance::Kilometers(2.0));
+    assert_eq!(start.style, ObsZoneStyle::ToNextPoint);
+    assert_eq!(start.r1, Some(Distance::Meters(1000.0)));
+    assert_eq!(start.line, Some(true));
+
+    let finish = ObservationZone::line(3, Distance::Meters(1000.0));
+    assert_eq!(finish.style, ObsZoneStyle::ToPreviousPoint);
+
+    let keyhole = ObservationZone::daec_keyhole(2);
+    assert_eq!(keyhole.r1, Some(Distance::Kilometers(10.0)));
+    assert_eq!(keyhole.a1, Some(45.0));
+    assert_eq!(keyhole.r2, Some(Distance::Meters(500.0)));
+    assert_eq!(keyhole.a2, Some(180.0));
+
+    let area = ObservationZone::aat_sector(1, Distance::Kilometers(30.0), 90.0, 60.0);
+    assert_eq!(area.style, ObsZoneStyle::Fixed);
+    assert_eq!(area.a12, Some(90.0));
+}
+
+#[test]
+fn test_classify_presets() {
+    let cases = [
+        (
+            ObservationZone::cylinder(1, Distance::Meters(500.0)),
+            ZonePreset::Cylinder {
+                radius: Distance::Meters(500.0),
+            },
+        ),
+        (
+            ObservationZone::line(0, Distance::Kilometers(10.0)),
+            ZonePreset::Line {
+                length: Distance::Meters(10_000.0),
+            },
+        ),
+        (
+            ObservationZone::fai_sector(1, Distance::Kilometers(20.0)),
+            ZonePreset::FaiSector {
+                radius: Distance::Meters(20_000.0),
+            },
+        ),
+        (
+            ObservationZone::bga_fixed_course(1),
+            ZonePreset::Keyhole {
+                sector_radius: Distance::Meters(20_000.0),
+                cylinder_radius: Distance::Meters(500.0),
+            },
+        ),
+        (
+            ObservationZone::bga_enhanced_option(1),
+            ZonePreset::BgaEnhancedOption,
+        ),
+        (
+            ObservationZone::aat_sector(1, Distance::Kilometers(30.0), 90.0, 60.0),
+            ZonePreset::FixedSector {
+                radius: Distance::Meters(30_000.0),
+                direction: 90.0,
+                half_angle: 60.0,
+            },
+        ),
+    ];
+
+    for (zone, expected) in cases {
+        assert_some_eq!(zone.classify(), expected);
+    }
+}
+
+#[test]
+fn test_classify_unknown_shapes() {
+    let mut zone = ObservationZone::fai_sector(1, Distance::Kilometers(20.0));
+    zone.a1 = Some(30.0);
+    assert_none!(zone.classify());
+
+    let mut zone = ObservationZone::cylinder(1, Distance::Meters(500.0));
+    zone.r1 = None;
+    assert_none!(zone.classify());
+
+    // Sector combined with a larger sector
+    let mut zone = ObservationZone::keyhole(1, Distance::Meters(500.0), Distance::Kilometers(1.0));
+    zone.a2 = Some(60.0);
+    assert_none!(zone.classify());
+}
+
+#[test]
+fn test_classify_sector_within_cylinder() {
+    // The sector lies within the cylinder of the second radius
+    for r2 in [Distance::Meters(500.0), Distance::Kilometers(3.0)] {
+        let zone = ObservationZone::keyhole(1, Distance::Meters(500.0), r2.clone());
+        let radius = Distance::Meters(r2.to_meters());
+        assert_some_eq!(zone.classify(), ZonePreset::Cylinder { radius });
+    }
+}
+
+#[test]
+fn test_classify_parsed_zones() {
+    let path = Path::new("tests/fixtures/709-km-Dreieck-DMSt-Aachen-Stolberg-TV.cup");
+    let (cup, _) = assert_ok!(CupFile::from_path(path));
+
+    let presets: Vec<_> = cup.tasks[0]
+        .observation_zones
+        .iter()
+        .map(ObservationZone::classify)
+        .collect();
+
+    let cylinder = Some(ZonePreset::Cylinder {
+        radius: Distance::Meters(1000.0),
+    });
+    let sector = Some(ZonePreset::FaiSector {
+        radius: Distance::Meters(20_000.0),
+    });
+    assert_eq!(
+        presets,
+        vec![
+            cylinder.clone(),
+            sector.clone(),
+            sector.clone(),
+            sector,
+            cylinder
+        ]
+    );
+}
+
+#[test]
+fn test_presets_roundtrip() {
+    let task = task_with_zones(vec![
+        ObservationZone::line(0, Distance::Kilometers(10.0)),
+        ObservationZone::daec_keyhole(1),
+        ObservationZone::cylinder(2, Distance::Kilometers(3.0)),
+    ]);
+    let cup = CupFile {
+        tasks: vec![task],
+        ..CupFile::default()
+    };
+
+    let output = assert_ok!(cup.to_string());
+    let (parsed, _) = assert_ok!(CupFile::from_str(&output));
+
+    let presets: Vec<_> = parsed.tasks[0]
+        .observation_zones
+        .iter()
+        .map(ObservationZone::classify)
+        .collect();
+    let expected: Vec<_> = cup.tasks[0]
+        .observation_zones
+        .iter()
+        .map(ObservationZone::classify)
+        .collect();
+    assert_eq!(presets, expected);
+}